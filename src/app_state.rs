@@ -1,10 +1,32 @@
+use chrono::Local;
 use ratatui::widgets::TableState;
-use std::collections::HashMap;
-use tokio::sync::mpsc;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use tokio::sync::{mpsc, watch};
 
+use crate::compose::{
+    ComposeAction, ComposeOperation, ComposeProgress, ComposeTarget, run_compose_action,
+};
+use crate::diff::{ContainerChanges, fetch_container_changes};
 use crate::docker::DockerHost;
+use crate::exec::{ExecLine, ExecSession, ExecStatus, push_history, run_exec_command};
+use crate::files::{
+    FileBrowser, FileKind, MAX_LINK_HOPS, PathContents, browse_container_path,
+    download_container_path, expand_destination, join_container_path, parent_container_path,
+    resolve_link_target,
+};
+use crate::fleet::{FleetService, build_fleet};
+use crate::grouping::{GroupKey, GroupSummary, ListRow, build_list_rows};
+use crate::health::{Health, HealthCheck, fetch_health_log};
+use crate::history::{HistorySample, StatsHistory, chart_window_count};
+use crate::host::HostInfo;
 use crate::logs::{LogEntry, stream_container_logs};
-use crate::types::{AppEvent, Container, ContainerKey, ViewState};
+use crate::prompt::{Prompt, PromptAction};
+use crate::smoothing::{SMOOTHING_PRESETS, Smoothing, SmoothingConfig};
+use crate::top::{ProcessInfo, ProcessList, ProcessSort, poll_container_processes};
+use crate::types::{
+    AppEvent, Container, ContainerKey, CpuMode, GroupMode, HostId, InteractiveSession, ViewState,
+};
 
 /// Application state that manages all runtime data
 pub struct AppState {
@@ -20,9 +42,9 @@ pub struct AppState {
     pub view_state: ViewState,
     /// Current container logs (only one container at a time)
     pub current_logs: Option<(ContainerKey, Vec<LogEntry>)>,
-    /// Current scroll position (number of lines scrolled from top)
+    /// Current scroll position (number of lines scrolled from top) in the log or exec view
     pub log_scroll_offset: usize,
-    /// Whether the user is at the bottom of the logs (for auto-scroll behavior)
+    /// Whether the user is at the bottom of the logs or exec output (for auto-scroll behavior)
     pub is_at_bottom: bool,
     /// Handle to the currently running log stream task
     pub log_stream_handle: Option<tokio::task::JoinHandle<()>>,
@@ -30,6 +52,73 @@ pub struct AppState {
     pub connected_hosts: HashMap<String, DockerHost>,
     /// Event sender for spawning log streams
     pub event_tx: mpsc::Sender<AppEvent>,
+    /// Interactive session to start (picked up by the event loop)
+    pub pending_session: Option<InteractiveSession>,
+    /// Message shown at the bottom of the container list (e.g. shell errors)
+    pub status_message: Option<String>,
+    /// Text prompt being typed (captures all character input while open)
+    pub prompt: Option<Prompt>,
+    /// Recently run commands per container, oldest first
+    pub exec_history: HashMap<ContainerKey, Vec<String>>,
+    /// Output of the most recent exec command
+    pub current_exec: Option<ExecSession>,
+    /// Handle to the currently running exec command task
+    pub exec_handle: Option<tokio::task::JoinHandle<()>>,
+    /// Process list for the container shown in the process view
+    pub current_processes: Option<ProcessList>,
+    /// Selection state of the process table
+    pub process_table_state: TableState,
+    /// Handle to the process list polling task
+    pub process_poll_handle: Option<tokio::task::JoinHandle<()>>,
+    /// File browser for the container shown in the file view
+    pub file_browser: Option<FileBrowser>,
+    /// Selection state of the file listing
+    pub file_table_state: TableState,
+    /// Changes of the container shown in the changes view (None while loading)
+    pub current_changes: Option<Result<ContainerChanges, String>>,
+    /// Selection state of the changes table
+    pub changes_table_state: TableState,
+    /// Scroll position (lines from top) in the detail view
+    pub detail_scroll_offset: usize,
+    /// Recent health checks of the container in the detail view (None while loading)
+    pub current_health_log: Option<Result<Vec<HealthCheck>, String>>,
+    /// How CPU usage is shown in the container list
+    pub cpu_mode: CpuMode,
+    /// Recent stats samples per container
+    pub stats_history: HashMap<ContainerKey, StatsHistory>,
+    /// Number of samples kept in each container's history
+    pub history_length: usize,
+    /// Index into CHART_WINDOWS of the time window shown in the chart view
+    pub chart_window: usize,
+    /// How containers are grouped in the container list
+    pub group_mode: GroupMode,
+    /// Groups whose containers are hidden in the container list
+    pub collapsed_groups: HashSet<GroupKey>,
+    /// Compose action waiting to be confirmed with 'y' (captures all character input while open)
+    pub compose_confirmation: Option<(ComposeTarget, ComposeAction)>,
+    /// Most recent Compose action and its progress per container
+    pub compose_operation: Option<ComposeOperation>,
+    /// Target of the last Compose stop, which 'U' starts again once its containers have left
+    /// the list
+    pub stopped_compose_target: Option<ComposeTarget>,
+    /// Whether the key help is shown over the container list ('?' key, closed by any key)
+    pub show_help: bool,
+    /// Host whose containers are the only ones listed (None lists all hosts)
+    pub host_filter: Option<HostId>,
+    /// Selection state of the host overview
+    pub host_table_state: TableState,
+    /// Label identifying a service across hosts in the fleet view (None matches by name)
+    pub fleet_label: Option<String>,
+    /// Selection state of the fleet view
+    pub fleet_table_state: TableState,
+    /// Latest capacity and container counts (or the error fetching them) per host
+    pub host_info: HashMap<HostId, Result<HostInfo, String>>,
+    /// Publishes smoothing changes to the stats streams
+    pub smoothing_tx: watch::Sender<SmoothingConfig>,
+    /// Smoothing from the config file, restored by the first preset
+    pub configured_smoothing: SmoothingConfig,
+    /// Index into SMOOTHING_PRESETS of the smoothing in use
+    pub smoothing_preset: usize,
 }
 
 impl AppState {
@@ -37,7 +126,12 @@ impl AppState {
     pub fn new(
         connected_hosts: HashMap<String, DockerHost>,
         event_tx: mpsc::Sender<AppEvent>,
+        smoothing_tx: watch::Sender<SmoothingConfig>,
+        history_length: usize,
+        fleet_label: Option<String>,
     ) -> Self {
+        let configured_smoothing = *smoothing_tx.borrow();
+
         Self {
             containers: HashMap::new(),
             sorted_container_keys: Vec::new(),
@@ -50,9 +144,95 @@ impl AppState {
             log_stream_handle: None,
             connected_hosts,
             event_tx,
+            pending_session: None,
+            status_message: None,
+            prompt: None,
+            exec_history: HashMap::new(),
+            current_exec: None,
+            exec_handle: None,
+            current_processes: None,
+            process_table_state: TableState::default(),
+            process_poll_handle: None,
+            file_browser: None,
+            file_table_state: TableState::default(),
+            current_changes: None,
+            changes_table_state: TableState::default(),
+            detail_scroll_offset: 0,
+            current_health_log: None,
+            cpu_mode: CpuMode::default(),
+            stats_history: HashMap::new(),
+            history_length,
+            chart_window: 1.min(chart_window_count(history_length) - 1),
+            group_mode: GroupMode::default(),
+            collapsed_groups: HashSet::new(),
+            compose_confirmation: None,
+            stopped_compose_target: None,
+            show_help: false,
+            compose_operation: None,
+            host_filter: None,
+            host_table_state: TableState::default(),
+            fleet_label,
+            fleet_table_state: TableState::default(),
+            host_info: HashMap::new(),
+            smoothing_tx,
+            configured_smoothing,
+            smoothing_preset: 0,
+        }
+    }
+
+    /// Returns the key of the currently selected container in the list
+    /// None when a group header is selected
+    pub fn selected_container_key(&self) -> Option<&ContainerKey> {
+        match self.selected_row()? {
+            ListRow::Container(key) => self.containers.get_key_value(&key).map(|(key, _)| key),
+            ListRow::Group(_) => None,
         }
     }
 
+    /// Returns the selected row of the container list
+    pub fn selected_row(&self) -> Option<ListRow> {
+        let idx = self.table_state.selected()?;
+        self.list_rows().into_iter().nth(idx)
+    }
+
+    /// Rows of the container list - containers, and group headers when grouping
+    pub fn list_rows(&self) -> Vec<ListRow> {
+        build_list_rows(
+            &self.visible_container_keys(),
+            &self.containers,
+            self.group_mode,
+            &self.collapsed_groups,
+        )
+    }
+
+    /// Sorted keys of the containers listed, limited to the filtered host if any
+    pub fn visible_container_keys(&self) -> Vec<&ContainerKey> {
+        self.sorted_container_keys
+            .iter()
+            .filter(|key| {
+                self.host_filter
+                    .as_ref()
+                    .is_none_or(|host_id| &key.host_id == host_id)
+            })
+            .collect()
+    }
+
+    /// Services across all connected hosts, as shown in the fleet view
+    pub fn fleet(&self) -> Vec<FleetService> {
+        build_fleet(
+            &self.containers,
+            &self.sorted_host_ids(),
+            self.fleet_label.as_deref(),
+        )
+    }
+
+    /// IDs of all connected hosts, sorted
+    pub fn sorted_host_ids(&self) -> Vec<&HostId> {
+        let mut hosts: Vec<&HostId> = self.connected_hosts.keys().collect();
+        hosts.sort();
+        hosts
+    }
+
     /// Processes a single event and returns whether UI should be redrawn
     pub fn handle_event(&mut self, event: AppEvent) -> bool {
         match event {
@@ -62,6 +242,25 @@ impl AppState {
             AppEvent::ContainerCreated(container) => self.handle_container_created(container),
             AppEvent::ContainerDestroyed(key) => self.handle_container_destroyed(key),
             AppEvent::ContainerStat(key, stats) => self.handle_container_stat(key, stats),
+            AppEvent::ContainerHealth(key, health) => self.handle_container_health(key, health),
+            AppEvent::ContainerPaused(key) => {
+                self.update_container(&key, |c| c.status = "paused".to_string())
+            }
+            AppEvent::ContainerUnpaused(key) => {
+                self.update_container(&key, |c| c.status = "running".to_string())
+            }
+            AppEvent::ContainerRestarted(key) => self.update_container(&key, |c| {
+                c.status = "running".to_string();
+                c.lifecycle.restarted_at = Some(Local::now());
+            }),
+            AppEvent::ContainerOutOfMemory(key) => {
+                self.update_container(&key, |c| c.lifecycle.out_of_memory = true)
+            }
+            AppEvent::ContainerKilled(key, signal) => {
+                self.update_container(&key, |c| c.lifecycle.last_signal = Some(signal))
+            }
+            AppEvent::ContainerRenamed(key, name) => self.handle_container_renamed(key, name),
+            AppEvent::HealthLog(key, result) => self.handle_health_log(key, result),
             AppEvent::Resize => true, // Always redraw on resize
             AppEvent::Quit => {
                 self.should_quit = true;
@@ -70,10 +269,22 @@ impl AppState {
             AppEvent::SelectPrevious => self.handle_select_previous(),
             AppEvent::SelectNext => self.handle_select_next(),
             AppEvent::EnterPressed => self.handle_enter_pressed(),
-            AppEvent::ExitLogView => self.handle_exit_log_view(),
+            AppEvent::EscapePressed => self.handle_escape_pressed(),
+            AppEvent::CharInput(c) => self.handle_char_input(c),
+            AppEvent::Backspace => self.handle_backspace(),
             AppEvent::ScrollUp => self.handle_scroll_up(),
             AppEvent::ScrollDown => self.handle_scroll_down(),
             AppEvent::LogLine(key, log_line) => self.handle_log_line(key, log_line),
+            AppEvent::ExecOutput(key, line) => self.handle_exec_output(key, line),
+            AppEvent::ExecFinished(key, status) => self.handle_exec_finished(key, status),
+            AppEvent::ProcessList(key, result) => self.handle_process_list(key, result),
+            AppEvent::PathContents(key, path, result) => {
+                self.handle_path_contents(key, path, result)
+            }
+            AppEvent::DownloadFinished(key, result) => self.handle_download_finished(key, result),
+            AppEvent::ContainerChanges(key, result) => self.handle_container_changes(key, result),
+            AppEvent::HostInfo(host_id, result) => self.handle_host_info(host_id, result),
+            AppEvent::ComposeProgress(progress) => self.handle_compose_progress(progress),
         }
     }
 
@@ -109,40 +320,75 @@ impl AppState {
 
     fn handle_container_created(&mut self, container: Container) -> bool {
         let key = ContainerKey::new(container.host_id.clone(), container.id.clone());
-        let sort_key = (container.host_id.clone(), container.name.clone());
         self.containers.insert(key.clone(), container);
+        self.insert_sorted_key(key);
+
+        // Select first row if this is the first container
+        if self.containers.len() == 1 {
+            self.table_state.select(Some(0));
+        }
+
+        true // Force draw - table structure changed
+    }
+
+    /// Inserts a container's key at its position (by host and name) in the sorted keys
+    fn insert_sorted_key(&mut self, key: ContainerKey) {
+        let Some(container) = self.containers.get(&key) else {
+            return;
+        };
+        let sort_key = (&container.host_id, &container.name);
 
-        // Insert into sorted position
         let insert_pos = self
             .sorted_container_keys
             .binary_search_by(|probe_key| {
                 let probe_container = self.containers.get(probe_key).unwrap();
-                let probe_sort_key = (&probe_container.host_id, &probe_container.name);
-                probe_sort_key.cmp(&(&sort_key.0, &sort_key.1))
+                (&probe_container.host_id, &probe_container.name).cmp(&sort_key)
             })
             .unwrap_or_else(|pos| pos);
         self.sorted_container_keys.insert(insert_pos, key);
+    }
 
-        // Select first row if this is the first container
-        if self.containers.len() == 1 {
-            self.table_state.select(Some(0));
-        }
+    fn handle_container_renamed(&mut self, key: ContainerKey, name: String) -> bool {
+        let Some(container) = self.containers.get_mut(&key) else {
+            return false;
+        };
+        container.name = name;
 
-        true // Force draw - table structure changed
+        // Move the container to its new position in the name order
+        self.sorted_container_keys.retain(|k| k != &key);
+        self.insert_sorted_key(key);
+
+        true // Force draw - table order may have changed
+    }
+
+    /// Applies a lifecycle change to a container, returning whether it is monitored
+    fn update_container(
+        &mut self,
+        key: &ContainerKey,
+        update: impl FnOnce(&mut Container),
+    ) -> bool {
+        match self.containers.get_mut(key) {
+            Some(container) => {
+                update(container);
+                true
+            }
+            None => false,
+        }
     }
 
     fn handle_container_destroyed(&mut self, key: ContainerKey) -> bool {
         self.containers.remove(&key);
+        self.stats_history.remove(&key);
         self.sorted_container_keys.retain(|k| k != &key);
 
         // Adjust selection if needed
-        let container_count = self.containers.len();
-        if container_count == 0 {
+        let row_count = self.list_rows().len();
+        if row_count == 0 {
             self.table_state.select(None);
         } else if let Some(selected) = self.table_state.selected()
-            && selected >= container_count
+            && selected >= row_count
         {
-            self.table_state.select(Some(container_count - 1));
+            self.table_state.select(Some(row_count - 1));
         }
 
         true // Force draw - table structure changed
@@ -154,14 +400,93 @@ impl AppState {
         stats: crate::types::ContainerStats,
     ) -> bool {
         if let Some(container) = self.containers.get_mut(&key) {
+            self.stats_history
+                .entry(key)
+                .or_insert_with(|| StatsHistory::new(self.history_length))
+                .push(HistorySample::from_stats(&stats, Instant::now()));
             container.stats = stats;
         }
         false // No force draw - just stats update
     }
 
+    fn handle_container_health(&mut self, key: ContainerKey, health: Health) -> bool {
+        let Some(container) = self.containers.get_mut(&key) else {
+            return false;
+        };
+        container.health = health;
+
+        // A health status change means a new check ran, so refresh the shown log
+        if self.view_state == ViewState::DetailView(key) {
+            self.refresh_health_log();
+        }
+
+        true
+    }
+
+    fn handle_health_log(
+        &mut self,
+        key: ContainerKey,
+        result: Result<Vec<HealthCheck>, String>,
+    ) -> bool {
+        // Ignore results for a container that is no longer shown
+        if self.view_state != ViewState::DetailView(key) {
+            return false;
+        }
+
+        self.current_health_log = Some(result);
+
+        true
+    }
+
+    /// Fetches the health check log of the container in the detail view
+    fn refresh_health_log(&self) {
+        let ViewState::DetailView(container_key) = &self.view_state else {
+            return;
+        };
+
+        if let Some(host) = self.connected_hosts.get(&container_key.host_id) {
+            let host_clone = host.clone();
+            let container_id = container_key.container_id.clone();
+            let tx_clone = self.event_tx.clone();
+
+            tokio::spawn(async move {
+                fetch_health_log(host_clone, container_id, tx_clone).await;
+            });
+        }
+    }
+
     fn handle_select_previous(&mut self) -> bool {
-        let container_count = self.containers.len();
-        if container_count > 0 {
+        // ↑ browses command history while the prompt is open
+        if let Some(prompt) = &mut self.prompt {
+            let history = match prompt.action {
+                PromptAction::Exec => self.exec_history.get(&prompt.key).map(Vec::as_slice),
+                PromptAction::Download { .. } | PromptAction::OpenPath => None,
+            };
+            prompt.history_previous(history.unwrap_or_default());
+            return true;
+        }
+
+        if self.view_state == ViewState::HostList {
+            let selected = self.host_table_state.selected().unwrap_or(0);
+            self.host_table_state
+                .select(Some(selected.saturating_sub(1)));
+            return true;
+        }
+
+        if self.view_state == ViewState::FleetView {
+            let selected = self.fleet_table_state.selected().unwrap_or(0);
+            self.fleet_table_state
+                .select(Some(selected.saturating_sub(1)));
+            return true;
+        }
+
+        // Only move the container selection in the container list
+        if self.view_state != ViewState::ContainerList {
+            return false;
+        }
+
+        let row_count = self.list_rows().len();
+        if row_count > 0 {
             let selected = self.table_state.selected().unwrap_or(0);
             if selected > 0 {
                 self.table_state.select(Some(selected - 1));
@@ -171,10 +496,42 @@ impl AppState {
     }
 
     fn handle_select_next(&mut self) -> bool {
-        let container_count = self.containers.len();
-        if container_count > 0 {
+        if let Some(prompt) = &mut self.prompt {
+            let history = match prompt.action {
+                PromptAction::Exec => self.exec_history.get(&prompt.key).map(Vec::as_slice),
+                PromptAction::Download { .. } | PromptAction::OpenPath => None,
+            };
+            prompt.history_next(history.unwrap_or_default());
+            return true;
+        }
+
+        if self.view_state == ViewState::HostList {
+            let host_count = self.connected_hosts.len();
+            let selected = self.host_table_state.selected().unwrap_or(0);
+            if selected + 1 < host_count {
+                self.host_table_state.select(Some(selected + 1));
+            }
+            return true;
+        }
+
+        if self.view_state == ViewState::FleetView {
+            let service_count = self.fleet().len();
+            let selected = self.fleet_table_state.selected().unwrap_or(0);
+            if selected + 1 < service_count {
+                self.fleet_table_state.select(Some(selected + 1));
+            }
+            return true;
+        }
+
+        // Only move the container selection in the container list
+        if self.view_state != ViewState::ContainerList {
+            return false;
+        }
+
+        let row_count = self.list_rows().len();
+        if row_count > 0 {
             let selected = self.table_state.selected().unwrap_or(0);
-            if selected < container_count - 1 {
+            if selected < row_count - 1 {
                 self.table_state.select(Some(selected + 1));
             }
         }
@@ -182,17 +539,31 @@ impl AppState {
     }
 
     fn handle_enter_pressed(&mut self) -> bool {
+        // Enter submits the prompt while it is open
+        if self.prompt.is_some() {
+            return self.handle_submit_prompt();
+        }
+
+        if let ViewState::FileView(_) = self.view_state {
+            return self.handle_open_file_entry();
+        }
+
+        if self.view_state == ViewState::HostList {
+            return self.handle_open_host();
+        }
+
         // Only handle Enter in ContainerList view
         if self.view_state != ViewState::ContainerList {
             return false;
         }
 
-        // Get the selected container
-        let Some(selected_idx) = self.table_state.selected() else {
-            return false;
-        };
+        // Enter on a group header expands or collapses the group
+        if let Some(ListRow::Group(group)) = self.selected_row() {
+            return self.handle_toggle_group(group.key);
+        }
 
-        let Some(container_key) = self.sorted_container_keys.get(selected_idx) else {
+        // Get the selected container
+        let Some(container_key) = self.selected_container_key().cloned() else {
             return false;
         };
 
@@ -227,76 +598,1253 @@ impl AppState {
         true // Force draw - view changed
     }
 
-    fn handle_exit_log_view(&mut self) -> bool {
-        // Only handle Escape when in log view
-        if !matches!(self.view_state, ViewState::LogView(_)) {
+    fn handle_open_session(
+        &mut self,
+        session: impl FnOnce(ContainerKey) -> InteractiveSession,
+    ) -> bool {
+        // Only start sessions from the container list
+        if self.view_state != ViewState::ContainerList {
             return false;
         }
 
-        // Stop log streaming
-        if let Some(handle) = self.log_stream_handle.take() {
-            handle.abort();
+        let Some(container_key) = self.selected_container_key().cloned() else {
+            return false;
+        };
+
+        // The event loop suspends the TUI and runs the session
+        self.pending_session = Some(session(container_key));
+
+        true
+    }
+
+    fn handle_char_input(&mut self, c: char) -> bool {
+        // A focused prompt captures all typed characters
+        if let Some(prompt) = &mut self.prompt {
+            prompt.input.push(c);
+            return true;
         }
 
-        // Clear current logs
-        self.current_logs = None;
+        // A pending confirmation only accepts 'y' or 'n'
+        if self.compose_confirmation.is_some() {
+            return match c {
+                'y' => self.handle_confirm_compose_action(),
+                'n' => self.compose_confirmation.take().is_some(),
+                _ => false,
+            };
+        }
 
-        // Switch back to container list view
-        self.view_state = ViewState::ContainerList;
+        // Any key closes the key help
+        if self.show_help {
+            self.show_help = false;
+            return true;
+        }
 
-        true // Force draw - view changed
+        match c {
+            'q' => {
+                self.should_quit = true;
+                false
+            }
+            's' => self.handle_open_session(InteractiveSession::Shell),
+            'a' => self.handle_open_session(InteractiveSession::Attach),
+            'x' => self.handle_open_exec_prompt(),
+            't' => self.handle_open_process_view(),
+            'f' => self.handle_open_file_view(),
+            'i' => self.handle_open_detail_view(),
+            'g' if matches!(self.view_state, ViewState::FileView(_)) => {
+                self.handle_open_path_prompt()
+            }
+            'g' => self.handle_open_chart_view(),
+            'h' if self.view_state == ViewState::ContainerList => self.handle_open_host_list(),
+            'v' if self.view_state == ViewState::ContainerList => {
+                let has_services = !self.fleet().is_empty();
+                self.fleet_table_state.select(has_services.then_some(0));
+                self.view_state = ViewState::FleetView;
+                true // Force draw - view changed
+            }
+            'w' if matches!(self.view_state, ViewState::ChartView(_)) => {
+                // Windows longer than the configured history would only ever be partly filled
+                self.chart_window =
+                    (self.chart_window + 1) % chart_window_count(self.history_length);
+                true
+            }
+            'n' if self.view_state == ViewState::ContainerList => {
+                self.cpu_mode = self.cpu_mode.toggle();
+                true
+            }
+            'm' if self.view_state == ViewState::ContainerList => self.handle_cycle_smoothing(),
+            'R' if self.view_state == ViewState::ContainerList => {
+                self.handle_request_compose_action(ComposeAction::Restart)
+            }
+            'S' if self.view_state == ViewState::ContainerList => {
+                self.handle_request_compose_action(ComposeAction::Stop)
+            }
+            'U' if self.view_state == ViewState::ContainerList => {
+                self.handle_request_compose_action(ComposeAction::Start)
+            }
+            'p' if self.view_state == ViewState::ContainerList => {
+                self.group_mode = self.group_mode.next();
+                self.table_state
+                    .select((!self.list_rows().is_empty()).then_some(0));
+                true
+            }
+            'c' if self.view_state == ViewState::ContainerList => self.handle_open_changes_view(),
+            '?' if self.view_state == ViewState::ContainerList => {
+                self.show_help = true;
+                true
+            }
+            'r' if matches!(self.view_state, ViewState::ChangesView(_)) => {
+                self.handle_refresh_changes()
+            }
+            'd' if matches!(self.view_state, ViewState::FileView(_)) => {
+                self.handle_open_download_prompt()
+            }
+            'p' | 'u' | 'c' | 'm' | 'n' if matches!(self.view_state, ViewState::ProcessView(_)) => {
+                self.handle_process_sort(c)
+            }
+            _ => false,
+        }
     }
 
-    fn handle_scroll_up(&mut self) -> bool {
-        // Only handle scroll in log view
-        if !matches!(self.view_state, ViewState::LogView(_)) {
-            return false;
+    /// Asks to confirm an action on the selected Compose project (group header) or service
+    /// Start goes to the last stopped project or service, since stopped containers aren't listed
+    fn handle_request_compose_action(&mut self, action: ComposeAction) -> bool {
+        if self
+            .compose_operation
+            .as_ref()
+            .is_some_and(|operation| !operation.finished)
+        {
+            self.status_message = Some("A Compose action is still running".to_string());
+            return true;
         }
 
-        // Scroll up (decrease offset)
-        if self.log_scroll_offset > 0 {
-            self.log_scroll_offset = self.log_scroll_offset.saturating_sub(1);
-            self.is_at_bottom = false; // User scrolled away from bottom
-            return true; // Force draw
-        }
+        let target = match self.selected_row() {
+            Some(ListRow::Group(GroupSummary {
+                key:
+                    GroupKey::Project {
+                        host_id,
+                        project: Some(project),
+                    },
+                ..
+            })) => Some(ComposeTarget {
+                host_id,
+                project,
+                service: None,
+            }),
+            Some(ListRow::Group(_)) => None,
+            Some(ListRow::Container(key)) => self.containers.get(&key).and_then(|container| {
+                Some(ComposeTarget {
+                    host_id: key.host_id.clone(),
+                    project: container.compose_project()?.to_string(),
+                    service: Some(container.compose_service()?.to_string()),
+                })
+            }),
+            None => None,
+        };
+        let target = match action {
+            ComposeAction::Start => self.stopped_compose_target.clone().or(target),
+            _ => target,
+        };
 
-        false
+        let Some(target) = target else {
+            self.status_message = Some("Select a Compose project or service".to_string());
+            return true;
+        };
+
+        self.status_message = None;
+        self.compose_confirmation = Some((target, action));
+        true
     }
 
-    fn handle_scroll_down(&mut self) -> bool {
-        // Only handle scroll in log view
-        if !matches!(self.view_state, ViewState::LogView(_)) {
+    fn handle_confirm_compose_action(&mut self) -> bool {
+        let Some((target, action)) = self.compose_confirmation.take() else {
             return false;
+        };
+
+        let Some(host) = self.connected_hosts.get(&target.host_id) else {
+            self.status_message = Some(format!("Host '{}' is not connected", target.host_id));
+            return true;
+        };
+
+        match action {
+            ComposeAction::Stop => self.stopped_compose_target = Some(target.clone()),
+            _ if self.stopped_compose_target.as_ref() == Some(&target) => {
+                self.stopped_compose_target = None;
+            }
+            _ => {}
         }
 
-        // Only scroll if we have logs
-        if self.current_logs.is_some() {
-            // Increment scroll offset
-            self.log_scroll_offset = self.log_scroll_offset.saturating_add(1);
+        self.compose_operation = Some(ComposeOperation::new(target.clone(), action));
 
-            // Will be clamped in UI and is_at_bottom will be recalculated there
-            return true; // Force draw
+        let host_clone = host.clone();
+        let tx_clone = self.event_tx.clone();
+        tokio::spawn(async move {
+            run_compose_action(host_clone, target, action, tx_clone).await;
+        });
+
+        true
+    }
+
+    fn handle_compose_progress(&mut self, progress: ComposeProgress) -> bool {
+        if let Some(operation) = &mut self.compose_operation {
+            operation.apply(progress);
+        }
+        true
+    }
+
+    fn handle_toggle_group(&mut self, key: GroupKey) -> bool {
+        if !self.collapsed_groups.remove(&key) {
+            self.collapsed_groups.insert(key);
+        }
+        true // Force draw - rows changed
+    }
+
+    /// Switches every stats stream to the next smoothing preset
+    fn handle_cycle_smoothing(&mut self) -> bool {
+        self.smoothing_preset = (self.smoothing_preset + 1) % SMOOTHING_PRESETS.len();
+        let smoothing = match SMOOTHING_PRESETS[self.smoothing_preset] {
+            Some(preset) => SmoothingConfig::uniform(preset),
+            None => self.configured_smoothing,
+        };
+        self.smoothing_tx.send_replace(smoothing);
+        true
+    }
+
+    /// The preset in use, or None while the configured smoothing is applied
+    pub fn smoothing_preset(&self) -> Option<Smoothing> {
+        SMOOTHING_PRESETS[self.smoothing_preset]
+    }
+
+    fn handle_backspace(&mut self) -> bool {
+        if let Some(prompt) = &mut self.prompt {
+            prompt.input.pop();
+            return true;
+        }
+
+        // Backspace goes up a directory in the file browser
+        if let ViewState::FileView(_) = self.view_state
+            && let Some(browser) = &self.file_browser
+        {
+            let parent = parent_container_path(&browser.path);
+            self.browse_path(parent);
+            return true;
         }
 
         false
     }
 
-    fn handle_log_line(&mut self, key: ContainerKey, log_entry: LogEntry) -> bool {
-        // Only add log line if we're currently viewing this container's logs
-        if let Some((current_key, logs)) = &mut self.current_logs
-            && current_key == &key
+    fn handle_escape_pressed(&mut self) -> bool {
+        // Escape closes the prompt or confirmation first, then the key help, file preview or
+        // Compose progress, then the current view
+        if self.prompt.take().is_some() {
+            return true;
+        }
+
+        if self.compose_confirmation.take().is_some() {
+            return true;
+        }
+
+        if self.show_help {
+            self.show_help = false;
+            return true;
+        }
+
+        if let Some(browser) = &mut self.file_browser
+            && browser.preview.take().is_some()
         {
-            logs.push(log_entry);
+            return true;
+        }
 
-            // Only auto-scroll if user is at the bottom
-            if self.is_at_bottom {
-                // Scroll will be updated to show bottom in UI
+        // The progress of a Compose action stays open until it has finished
+        if self.view_state == ViewState::ContainerList
+            && let Some(operation) = &self.compose_operation
+        {
+            if operation.finished {
+                self.compose_operation = None;
+                return true;
             }
+            return false;
+        }
 
-            return true; // Force draw - new log line for currently viewed container
+        match self.view_state {
+            ViewState::LogView(_) => self.handle_exit_log_view(),
+            ViewState::ExecView(_) => self.handle_exit_exec_view(),
+            ViewState::ProcessView(_) => self.handle_exit_process_view(),
+            ViewState::FileView(_) => self.handle_exit_file_view(),
+            ViewState::ChangesView(_) => self.handle_exit_changes_view(),
+            ViewState::DetailView(_)
+            | ViewState::ChartView(_)
+            | ViewState::HostList
+            | ViewState::FleetView => {
+                self.view_state = ViewState::ContainerList;
+                true // Force draw - view changed
+            }
+            // Escape in a single host's container list goes back to all hosts
+            ViewState::ContainerList if self.host_filter.is_some() => {
+                self.set_host_filter(None);
+                true
+            }
+            ViewState::ContainerList => false,
         }
+    }
 
-        // Ignore log lines for containers we're not viewing
-        false
+    fn handle_open_exec_prompt(&mut self) -> bool {
+        // Commands run in the selected container, or the one whose output is shown
+        let container_key = match &self.view_state {
+            ViewState::ContainerList => self.selected_container_key().cloned(),
+            ViewState::ExecView(key) => Some(key.clone()),
+            ViewState::HostList
+            | ViewState::FleetView
+            | ViewState::LogView(_)
+            | ViewState::ProcessView(_)
+            | ViewState::FileView(_)
+            | ViewState::ChangesView(_)
+            | ViewState::DetailView(_)
+            | ViewState::ChartView(_) => None,
+        };
+
+        let Some(container_key) = container_key else {
+            return false;
+        };
+
+        self.prompt = Some(Prompt::new(
+            container_key,
+            PromptAction::Exec,
+            String::new(),
+        ));
+
+        true
+    }
+
+    fn handle_submit_prompt(&mut self) -> bool {
+        let Some(prompt) = self.prompt.take() else {
+            return false;
+        };
+
+        match prompt.action.clone() {
+            PromptAction::Exec => self.handle_submit_exec(prompt),
+            PromptAction::Download { path } => self.handle_submit_download(prompt, path),
+            PromptAction::OpenPath => self.handle_submit_open_path(prompt),
+        }
+    }
+
+    fn handle_submit_exec(&mut self, prompt: Prompt) -> bool {
+        let command = prompt.input.trim().to_string();
+        if command.is_empty() {
+            return true; // Force draw - prompt closed
+        }
+
+        push_history(
+            self.exec_history.entry(prompt.key.clone()).or_default(),
+            &command,
+        );
+
+        // Stop any previous command before starting a new one
+        if let Some(handle) = self.exec_handle.take() {
+            handle.abort();
+        }
+
+        self.current_exec = Some(ExecSession::new(prompt.key.clone(), command.clone()));
+        self.view_state = ViewState::ExecView(prompt.key.clone());
+
+        // Reset scroll state - start at bottom
+        self.log_scroll_offset = 0;
+        self.is_at_bottom = true;
+
+        if let Some(host) = self.connected_hosts.get(&prompt.key.host_id) {
+            let host_clone = host.clone();
+            let container_id = prompt.key.container_id.clone();
+            let tx_clone = self.event_tx.clone();
+
+            let handle = tokio::spawn(async move {
+                run_exec_command(host_clone, container_id, command, tx_clone).await;
+            });
+
+            self.exec_handle = Some(handle);
+        }
+
+        true // Force draw - view changed
+    }
+
+    fn handle_exit_exec_view(&mut self) -> bool {
+        // Stop the command if it is still running
+        if let Some(handle) = self.exec_handle.take() {
+            handle.abort();
+        }
+
+        self.current_exec = None;
+        self.view_state = ViewState::ContainerList;
+
+        true // Force draw - view changed
+    }
+
+    fn handle_exec_output(&mut self, key: ContainerKey, line: ExecLine) -> bool {
+        if let Some(session) = &mut self.current_exec
+            && session.key == key
+            && session.status.is_none()
+        {
+            session.lines.push(line);
+            return true;
+        }
+
+        false
+    }
+
+    fn handle_exec_finished(&mut self, key: ContainerKey, status: ExecStatus) -> bool {
+        if let Some(session) = &mut self.current_exec
+            && session.key == key
+            && session.status.is_none()
+        {
+            session.status = Some(status);
+            self.exec_handle = None;
+            return true;
+        }
+
+        false
+    }
+
+    fn handle_open_process_view(&mut self) -> bool {
+        // Only open the process view from the container list
+        if self.view_state != ViewState::ContainerList {
+            return false;
+        }
+
+        let Some(container_key) = self.selected_container_key().cloned() else {
+            return false;
+        };
+
+        self.view_state = ViewState::ProcessView(container_key.clone());
+        self.current_processes = Some(ProcessList::new(container_key.clone()));
+        self.process_table_state = TableState::default();
+
+        // Stop any existing poller
+        if let Some(handle) = self.process_poll_handle.take() {
+            handle.abort();
+        }
+
+        // Start polling the process list for this container
+        if let Some(host) = self.connected_hosts.get(&container_key.host_id) {
+            let host_clone = host.clone();
+            let container_id = container_key.container_id.clone();
+            let tx_clone = self.event_tx.clone();
+
+            let handle = tokio::spawn(async move {
+                poll_container_processes(host_clone, container_id, tx_clone).await;
+            });
+
+            self.process_poll_handle = Some(handle);
+        }
+
+        true // Force draw - view changed
+    }
+
+    fn handle_exit_process_view(&mut self) -> bool {
+        if let Some(handle) = self.process_poll_handle.take() {
+            handle.abort();
+        }
+
+        self.current_processes = None;
+        self.view_state = ViewState::ContainerList;
+
+        true // Force draw - view changed
+    }
+
+    fn handle_process_sort(&mut self, c: char) -> bool {
+        let sort = match c {
+            'p' => ProcessSort::Pid,
+            'u' => ProcessSort::User,
+            'c' => ProcessSort::Cpu,
+            'm' => ProcessSort::Memory,
+            _ => ProcessSort::Command,
+        };
+
+        if let Some(list) = &mut self.current_processes {
+            list.set_sort(sort);
+            return true;
+        }
+
+        false
+    }
+
+    fn handle_process_list(
+        &mut self,
+        key: ContainerKey,
+        result: Result<Vec<ProcessInfo>, String>,
+    ) -> bool {
+        let Some(list) = &mut self.current_processes else {
+            return false;
+        };
+
+        if list.key != key {
+            return false;
+        }
+
+        list.update(result);
+
+        // Keep the selection within the refreshed list
+        let process_count = list.processes.len();
+        match self.process_table_state.selected() {
+            _ if process_count == 0 => self.process_table_state.select(None),
+            None => self.process_table_state.select(Some(0)),
+            Some(selected) if selected >= process_count => {
+                self.process_table_state.select(Some(process_count - 1))
+            }
+            Some(_) => {}
+        }
+
+        true
+    }
+
+    fn handle_open_detail_view(&mut self) -> bool {
+        // Only open the detail view from the container list
+        if self.view_state != ViewState::ContainerList {
+            return false;
+        }
+
+        let Some(container_key) = self.selected_container_key().cloned() else {
+            return false;
+        };
+
+        self.view_state = ViewState::DetailView(container_key);
+        self.detail_scroll_offset = 0;
+        self.current_health_log = None;
+        self.refresh_health_log();
+
+        true // Force draw - view changed
+    }
+
+    fn handle_open_chart_view(&mut self) -> bool {
+        // Only open the charts from the container list
+        if self.view_state != ViewState::ContainerList {
+            return false;
+        }
+
+        let Some(container_key) = self.selected_container_key().cloned() else {
+            return false;
+        };
+
+        self.view_state = ViewState::ChartView(container_key);
+
+        true // Force draw - view changed
+    }
+
+    fn handle_open_changes_view(&mut self) -> bool {
+        let Some(container_key) = self.selected_container_key().cloned() else {
+            return false;
+        };
+
+        self.view_state = ViewState::ChangesView(container_key);
+        self.handle_refresh_changes()
+    }
+
+    fn handle_refresh_changes(&mut self) -> bool {
+        let ViewState::ChangesView(container_key) = &self.view_state else {
+            return false;
+        };
+
+        self.current_changes = None;
+        self.changes_table_state = TableState::default();
+
+        if let Some(host) = self.connected_hosts.get(&container_key.host_id) {
+            let host_clone = host.clone();
+            let container_id = container_key.container_id.clone();
+            let tx_clone = self.event_tx.clone();
+
+            tokio::spawn(async move {
+                fetch_container_changes(host_clone, container_id, tx_clone).await;
+            });
+        }
+
+        true // Force draw - view changed
+    }
+
+    fn handle_exit_changes_view(&mut self) -> bool {
+        self.current_changes = None;
+        self.view_state = ViewState::ContainerList;
+
+        true // Force draw - view changed
+    }
+
+    fn handle_container_changes(
+        &mut self,
+        key: ContainerKey,
+        result: Result<ContainerChanges, String>,
+    ) -> bool {
+        // Ignore results for a container that is no longer shown
+        if self.view_state != ViewState::ChangesView(key) {
+            return false;
+        }
+
+        if matches!(&result, Ok(changes) if changes.row_count() > 0) {
+            self.changes_table_state.select(Some(0));
+        }
+        self.current_changes = Some(result);
+
+        true
+    }
+
+    fn handle_open_host_list(&mut self) -> bool {
+        // Start on the host whose containers are listed, or the first host
+        let selected = self
+            .host_filter
+            .as_ref()
+            .and_then(|host_id| self.sorted_host_ids().iter().position(|h| *h == host_id))
+            .unwrap_or(0);
+
+        self.host_table_state.select(Some(selected));
+        self.view_state = ViewState::HostList;
+        true // Force draw - view changed
+    }
+
+    /// Drills into the selected host, listing only its containers
+    fn handle_open_host(&mut self) -> bool {
+        let host_id = self
+            .host_table_state
+            .selected()
+            .and_then(|idx| self.sorted_host_ids().get(idx).map(|h| h.to_string()));
+
+        let Some(host_id) = host_id else {
+            return false;
+        };
+
+        self.set_host_filter(Some(host_id));
+        self.view_state = ViewState::ContainerList;
+        true // Force draw - view changed
+    }
+
+    /// Limits the container list to a host (or lists all hosts) and resets the selection
+    fn set_host_filter(&mut self, host_filter: Option<HostId>) {
+        self.host_filter = host_filter;
+        let has_rows = !self.list_rows().is_empty();
+        self.table_state.select(has_rows.then_some(0));
+    }
+
+    fn handle_host_info(&mut self, host_id: HostId, result: Result<HostInfo, String>) -> bool {
+        // A host appearing adds a line to the summary header, so the layout changes
+        self.host_info.insert(host_id, result).is_none()
+    }
+
+    fn handle_open_file_view(&mut self) -> bool {
+        // Only open the file browser from the container list
+        if self.view_state != ViewState::ContainerList {
+            return false;
+        }
+
+        let Some(container_key) = self.selected_container_key().cloned() else {
+            return false;
+        };
+
+        self.view_state = ViewState::FileView(container_key.clone());
+        self.file_browser = Some(FileBrowser::new(container_key));
+        self.browse_path("/".to_string());
+
+        true // Force draw - view changed
+    }
+
+    fn handle_exit_file_view(&mut self) -> bool {
+        self.file_browser = None;
+        self.view_state = ViewState::ContainerList;
+
+        true // Force draw - view changed
+    }
+
+    /// Fetches a container path picked by the user for the file browser
+    fn browse_path(&mut self, path: String) {
+        if let Some(browser) = &mut self.file_browser {
+            browser.link_hops = 0;
+        }
+        self.fetch_path(path);
+    }
+
+    /// Fetches a container path for the file browser in the background
+    fn fetch_path(&mut self, path: String) {
+        let Some(browser) = &mut self.file_browser else {
+            return;
+        };
+
+        browser.loading = true;
+        browser.error = None;
+        browser.requested_path = Some(path.clone());
+
+        if let Some(host) = self.connected_hosts.get(&browser.key.host_id) {
+            let host_clone = host.clone();
+            let container_id = browser.key.container_id.clone();
+            let tx_clone = self.event_tx.clone();
+
+            tokio::spawn(async move {
+                browse_container_path(host_clone, container_id, path, tx_clone).await;
+            });
+        }
+    }
+
+    fn handle_open_file_entry(&mut self) -> bool {
+        let Some(browser) = &self.file_browser else {
+            return false;
+        };
+
+        let Some(entry) = self
+            .file_table_state
+            .selected()
+            .and_then(|idx| browser.entries.get(idx))
+        else {
+            return false;
+        };
+
+        let path = match &entry.kind {
+            _ if entry.name == ".." => parent_container_path(&browser.path),
+            FileKind::Symlink(target) => resolve_link_target(&browser.path, target),
+            _ => join_container_path(&browser.path, &entry.name),
+        };
+
+        self.browse_path(path);
+        true
+    }
+
+    fn handle_path_contents(
+        &mut self,
+        key: ContainerKey,
+        path: String,
+        result: Result<PathContents, String>,
+    ) -> bool {
+        let Some(browser) = &mut self.file_browser else {
+            return false;
+        };
+
+        // Ignore replies to requests the user has since navigated away from
+        if browser.key != key || browser.requested_path.as_deref() != Some(path.as_str()) {
+            return false;
+        }
+
+        browser.loading = false;
+        browser.requested_path = None;
+
+        match result {
+            Ok(PathContents::Directory { entries, truncated }) => {
+                browser.set_directory(path, entries, truncated);
+                self.file_table_state = TableState::default();
+                if !browser.entries.is_empty() {
+                    self.file_table_state.select(Some(0));
+                }
+            }
+            Ok(PathContents::File(preview)) => browser.preview = Some((path, preview)),
+            Ok(PathContents::Symlink(target)) => {
+                // Follow links until they resolve to a file or directory, or loop too often
+                browser.link_hops += 1;
+                if browser.link_hops > MAX_LINK_HOPS {
+                    browser.error = Some(format!("{}: Too many levels of symbolic links", path));
+                } else {
+                    let dir = parent_container_path(&path);
+                    self.fetch_path(resolve_link_target(&dir, &target));
+                }
+            }
+            Err(error) => browser.error = Some(format!("{}: {}", path, error)),
+        }
+
+        true
+    }
+
+    fn handle_open_download_prompt(&mut self) -> bool {
+        let Some(browser) = &self.file_browser else {
+            return false;
+        };
+
+        // Download the selected entry, or the current directory when ".." is selected
+        let path = match self
+            .file_table_state
+            .selected()
+            .and_then(|idx| browser.entries.get(idx))
+        {
+            Some(entry) if entry.name != ".." => join_container_path(&browser.path, &entry.name),
+            _ => browser.path.clone(),
+        };
+
+        self.prompt = Some(Prompt::new(
+            browser.key.clone(),
+            PromptAction::Download { path },
+            ".".to_string(),
+        ));
+
+        true
+    }
+
+    fn handle_open_path_prompt(&mut self) -> bool {
+        let Some(browser) = &self.file_browser else {
+            return false;
+        };
+
+        self.prompt = Some(Prompt::new(
+            browser.key.clone(),
+            PromptAction::OpenPath,
+            browser.path.clone(),
+        ));
+
+        true
+    }
+
+    fn handle_submit_open_path(&mut self, prompt: Prompt) -> bool {
+        let input = prompt.input.trim();
+        let Some(browser) = &self.file_browser else {
+            return true; // Force draw - prompt closed
+        };
+        if input.is_empty() {
+            return true;
+        }
+
+        // Relative paths are opened from the directory being listed
+        let path = resolve_link_target(&browser.path, input);
+        self.browse_path(path);
+
+        true
+    }
+
+    fn handle_submit_download(&mut self, prompt: Prompt, path: String) -> bool {
+        let destination = expand_destination(&prompt.input);
+        if destination.as_os_str().is_empty() {
+            return true; // Force draw - prompt closed
+        }
+
+        if let Some(browser) = &mut self.file_browser {
+            browser.message = Some(format!("Downloading {}...", path));
+            browser.error = None;
+        }
+
+        if let Some(host) = self.connected_hosts.get(&prompt.key.host_id) {
+            let host_clone = host.clone();
+            let container_id = prompt.key.container_id.clone();
+            let tx_clone = self.event_tx.clone();
+
+            tokio::spawn(async move {
+                download_container_path(host_clone, container_id, path, destination, tx_clone)
+                    .await;
+            });
+        }
+
+        true
+    }
+
+    fn handle_download_finished(
+        &mut self,
+        key: ContainerKey,
+        result: Result<String, String>,
+    ) -> bool {
+        let Some(browser) = &mut self.file_browser else {
+            return false;
+        };
+
+        if browser.key != key {
+            return false;
+        }
+
+        match result {
+            Ok(message) => browser.message = Some(message),
+            Err(error) => {
+                browser.message = None;
+                browser.error = Some(error);
+            }
+        }
+
+        true
+    }
+
+    fn handle_exit_log_view(&mut self) -> bool {
+        // Only handle Escape when in log view
+        if !matches!(self.view_state, ViewState::LogView(_)) {
+            return false;
+        }
+
+        // Stop log streaming
+        if let Some(handle) = self.log_stream_handle.take() {
+            handle.abort();
+        }
+
+        // Clear current logs
+        self.current_logs = None;
+
+        // Switch back to container list view
+        self.view_state = ViewState::ContainerList;
+
+        true // Force draw - view changed
+    }
+
+    fn handle_scroll_up(&mut self) -> bool {
+        // ↑ moves the file selection in the file browser
+        if let ViewState::FileView(_) = self.view_state {
+            if self.prompt.is_none() {
+                let selected = self.file_table_state.selected().unwrap_or(0);
+                self.file_table_state
+                    .select(Some(selected.saturating_sub(1)));
+            }
+            return true;
+        }
+
+        // ↑ scrolls the detail view
+        if let ViewState::DetailView(_) = self.view_state {
+            self.detail_scroll_offset = self.detail_scroll_offset.saturating_sub(1);
+            return true;
+        }
+
+        // ↑ moves the selection in the changes view
+        if let ViewState::ChangesView(_) = self.view_state {
+            let selected = self.changes_table_state.selected().unwrap_or(0);
+            self.changes_table_state
+                .select(Some(selected.saturating_sub(1)));
+            return true;
+        }
+
+        // ↑ moves the process selection in the process view
+        if let ViewState::ProcessView(_) = self.view_state {
+            let selected = self.process_table_state.selected().unwrap_or(0);
+            self.process_table_state
+                .select(Some(selected.saturating_sub(1)));
+            return true;
+        }
+
+        // Only handle scroll in log and exec views, and not while the prompt is open
+        if !self.is_scrollable_view() {
+            return false;
+        }
+
+        // Scroll up (decrease offset)
+        if self.log_scroll_offset > 0 {
+            self.log_scroll_offset = self.log_scroll_offset.saturating_sub(1);
+            self.is_at_bottom = false; // User scrolled away from bottom
+            return true; // Force draw
+        }
+
+        false
+    }
+
+    fn handle_scroll_down(&mut self) -> bool {
+        if let ViewState::FileView(_) = self.view_state {
+            let entry_count = self
+                .file_browser
+                .as_ref()
+                .map_or(0, |browser| browser.entries.len());
+            let selected = self.file_table_state.selected().unwrap_or(0);
+            if self.prompt.is_none() && selected + 1 < entry_count {
+                self.file_table_state.select(Some(selected + 1));
+            }
+            return true;
+        }
+
+        if let ViewState::DetailView(_) = self.view_state {
+            // Will be clamped to the content height in the UI
+            self.detail_scroll_offset = self.detail_scroll_offset.saturating_add(1);
+            return true;
+        }
+
+        if let ViewState::ChangesView(_) = self.view_state {
+            let row_count = match &self.current_changes {
+                Some(Ok(changes)) => changes.row_count(),
+                _ => 0,
+            };
+            let selected = self.changes_table_state.selected().unwrap_or(0);
+            if selected + 1 < row_count {
+                self.changes_table_state.select(Some(selected + 1));
+            }
+            return true;
+        }
+
+        if let ViewState::ProcessView(_) = self.view_state {
+            let process_count = self
+                .current_processes
+                .as_ref()
+                .map_or(0, |list| list.processes.len());
+            let selected = self.process_table_state.selected().unwrap_or(0);
+            if selected + 1 < process_count {
+                self.process_table_state.select(Some(selected + 1));
+            }
+            return true;
+        }
+
+        // Only handle scroll in log and exec views, and not while the prompt is open
+        if !self.is_scrollable_view() {
+            return false;
+        }
+
+        // Only scroll if we have logs or exec output
+        if self.current_logs.is_some() || self.current_exec.is_some() {
+            // Increment scroll offset
+            self.log_scroll_offset = self.log_scroll_offset.saturating_add(1);
+
+            // Will be clamped in UI and is_at_bottom will be recalculated there
+            return true; // Force draw
+        }
+
+        false
+    }
+
+    fn is_scrollable_view(&self) -> bool {
+        self.prompt.is_none()
+            && matches!(
+                self.view_state,
+                ViewState::LogView(_) | ViewState::ExecView(_)
+            )
+    }
+
+    fn handle_log_line(&mut self, key: ContainerKey, log_entry: LogEntry) -> bool {
+        // Only add log line if we're currently viewing this container's logs
+        if let Some((current_key, logs)) = &mut self.current_logs
+            && current_key == &key
+        {
+            logs.push(log_entry);
+
+            // Only auto-scroll if user is at the bottom
+            if self.is_at_bottom {
+                // Scroll will be updated to show bottom in UI
+            }
+
+            return true; // Force draw - new log line for currently viewed container
+        }
+
+        // Ignore log lines for containers we're not viewing
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::FileEntry;
+    use crate::grouping::{COMPOSE_PROJECT_LABEL, COMPOSE_SERVICE_LABEL};
+    use crate::history::DEFAULT_HISTORY_LENGTH;
+    use bollard::{API_DEFAULT_VERSION, Docker};
+
+    fn state() -> AppState {
+        let (event_tx, _) = mpsc::channel(16);
+        let (smoothing_tx, _) = watch::channel(SmoothingConfig::default());
+        AppState::new(
+            HashMap::new(),
+            event_tx,
+            smoothing_tx,
+            DEFAULT_HISTORY_LENGTH,
+            None,
+        )
+    }
+
+    fn key(id: &str) -> ContainerKey {
+        ContainerKey::new("local".to_string(), id.to_string())
+    }
+
+    fn compose_container(id: &str, project: &str, service: &str) -> Container {
+        Container {
+            labels: HashMap::from([
+                (COMPOSE_PROJECT_LABEL.to_string(), project.to_string()),
+                (COMPOSE_SERVICE_LABEL.to_string(), service.to_string()),
+            ]),
+            ..Container::for_test("local", id)
+        }
+    }
+
+    /// State listing the containers of the "shop" project, "web" and "db"
+    fn shop_state() -> AppState {
+        let mut state = state();
+        state.handle_event(AppEvent::InitialContainerList(
+            "local".to_string(),
+            vec![
+                compose_container("web", "shop", "web"),
+                compose_container("db", "shop", "db"),
+            ],
+        ));
+        state
+    }
+
+    #[test]
+    fn test_enter_on_group_header_collapses_it() {
+        let mut state = shop_state();
+        state.group_mode = GroupMode::Compose;
+        state.table_state.select(Some(0));
+
+        assert!(state.handle_event(AppEvent::EnterPressed));
+
+        assert_eq!(state.list_rows().len(), 1);
+        assert_eq!(state.table_state.selected(), Some(0));
+        assert!(matches!(state.selected_row(), Some(ListRow::Group(_))));
+        assert!(matches!(state.view_state, ViewState::ContainerList));
+
+        assert!(state.handle_event(AppEvent::EnterPressed));
+        assert_eq!(state.list_rows().len(), 3);
+    }
+
+    #[test]
+    fn test_container_actions_ignore_group_headers() {
+        let mut state = shop_state();
+        state.group_mode = GroupMode::Compose;
+        state.table_state.select(Some(0));
+
+        assert_eq!(state.selected_container_key(), None);
+        assert!(!state.handle_event(AppEvent::CharInput('s')));
+        assert!(state.pending_session.is_none());
+
+        state.table_state.select(Some(1));
+        assert!(state.selected_container_key().is_some());
+    }
+
+    #[test]
+    fn test_destroying_last_row_clamps_selection() {
+        let mut state = shop_state();
+        state.table_state.select(Some(1));
+
+        state.handle_event(AppEvent::ContainerDestroyed(
+            state.selected_container_key().unwrap().clone(),
+        ));
+        assert_eq!(state.table_state.selected(), Some(0));
+
+        state.handle_event(AppEvent::ContainerDestroyed(
+            state.selected_container_key().unwrap().clone(),
+        ));
+        assert_eq!(state.table_state.selected(), None);
+    }
+
+    #[test]
+    fn test_group_mode_resets_selection() {
+        let mut state = shop_state();
+        state.table_state.select(Some(1));
+
+        assert!(state.handle_event(AppEvent::CharInput('p')));
+
+        assert_eq!(state.group_mode, GroupMode::Compose);
+        assert_eq!(state.table_state.selected(), Some(0));
+        assert!(matches!(state.selected_row(), Some(ListRow::Group(_))));
+    }
+
+    #[test]
+    fn test_rename_moves_container_to_sorted_position() {
+        let mut state = shop_state();
+        assert_eq!(state.sorted_container_keys, vec![key("db"), key("web")]);
+
+        assert!(state.handle_event(AppEvent::ContainerRenamed(key("db"), "zz-db".to_string())));
+
+        assert_eq!(state.containers[&key("db")].name, "zz-db");
+        assert_eq!(state.sorted_container_keys, vec![key("web"), key("db")]);
+    }
+
+    #[test]
+    fn test_pause_and_unpause_update_status() {
+        let mut state = shop_state();
+
+        assert!(state.handle_event(AppEvent::ContainerPaused(key("web"))));
+        assert_eq!(state.containers[&key("web")].status, "paused");
+
+        assert!(state.handle_event(AppEvent::ContainerUnpaused(key("web"))));
+        assert_eq!(state.containers[&key("web")].status, "running");
+    }
+
+    #[test]
+    fn test_events_for_unknown_containers_are_ignored() {
+        let mut state = shop_state();
+
+        assert!(!state.handle_event(AppEvent::ContainerPaused(key("gone"))));
+        assert!(!state.handle_event(AppEvent::ContainerRenamed(key("gone"), "web".to_string())));
+        assert!(!state.handle_event(AppEvent::ContainerKilled(key("gone"), "15".to_string())));
+
+        assert_eq!(state.containers.len(), 2);
+        assert_eq!(state.sorted_container_keys, vec![key("db"), key("web")]);
+    }
+
+    #[test]
+    fn test_symlink_cycle_stops_following() {
+        let mut state = state();
+        let key = key("abc");
+        state.view_state = ViewState::FileView(key.clone());
+        state.file_browser = Some(FileBrowser::new(key.clone()));
+        state.browse_path("/a".to_string());
+
+        // /a -> b -> a -> ...
+        for hop in 0..=MAX_LINK_HOPS {
+            let (path, target) = if hop % 2 == 0 {
+                ("/a", "b")
+            } else {
+                ("/b", "a")
+            };
+            state.handle_event(AppEvent::PathContents(
+                key.clone(),
+                path.to_string(),
+                Ok(PathContents::Symlink(target.to_string())),
+            ));
+        }
+
+        let browser = state.file_browser.as_ref().unwrap();
+        assert!(!browser.loading);
+        assert!(
+            browser
+                .error
+                .as_deref()
+                .is_some_and(|error| error.ends_with("Too many levels of symbolic links"))
+        );
+
+        // Picking a path again starts counting from zero
+        state.browse_path("/a".to_string());
+        assert_eq!(state.file_browser.as_ref().unwrap().link_hops, 0);
+    }
+
+    #[test]
+    fn test_stale_path_contents_are_ignored() {
+        let mut state = state();
+        let key = key("abc");
+        state.view_state = ViewState::FileView(key.clone());
+        state.file_browser = Some(FileBrowser::new(key.clone()));
+
+        // Enter on /etc, then Backspace back to / before /etc is listed
+        state.browse_path("/etc".to_string());
+        state.browse_path("/".to_string());
+
+        let listing = |name: &str| {
+            Ok(PathContents::Directory {
+                entries: vec![FileEntry {
+                    name: name.to_string(),
+                    kind: FileKind::File,
+                    size: 0,
+                }],
+                truncated: false,
+            })
+        };
+        assert!(state.handle_event(AppEvent::PathContents(
+            key.clone(),
+            "/".to_string(),
+            listing("root")
+        )));
+        assert!(!state.handle_event(AppEvent::PathContents(
+            key.clone(),
+            "/etc".to_string(),
+            listing("hosts")
+        )));
+
+        let browser = state.file_browser.as_ref().unwrap();
+        assert_eq!(browser.path, "/");
+        assert_eq!(browser.entries[0].name, "root");
+        assert!(!browser.loading);
+    }
+
+    #[tokio::test]
+    async fn test_start_reachable_after_stop() {
+        // Never connects - the spawned Compose actions fail without affecting the state tested
+        let docker =
+            Docker::connect_with_http("http://127.0.0.1:1", 1, API_DEFAULT_VERSION).unwrap();
+        let mut state = state();
+        state.connected_hosts.insert(
+            "local".to_string(),
+            DockerHost::new("local".to_string(), docker),
+        );
+
+        state.handle_event(AppEvent::InitialContainerList(
+            "local".to_string(),
+            vec![compose_container("web", "shop", "web")],
+        ));
+        state.table_state.select(Some(0));
+
+        state.handle_event(AppEvent::CharInput('S'));
+        state.handle_event(AppEvent::CharInput('y'));
+        state.handle_event(AppEvent::ContainerDestroyed(key("web")));
+        state.compose_operation.as_mut().unwrap().finished = true;
+        assert!(state.list_rows().is_empty());
+
+        state.handle_event(AppEvent::CharInput('U'));
+        let (target, action) = state.compose_confirmation.clone().unwrap();
+        assert_eq!(action, ComposeAction::Start);
+        assert_eq!(target.project, "shop");
+        assert_eq!(target.service.as_deref(), Some("web"));
+
+        state.handle_event(AppEvent::CharInput('y'));
+        assert_eq!(state.stopped_compose_target, None);
     }
 }
@@ -0,0 +1,271 @@
+use bollard::query_parameters::{
+    ListContainersOptions, RestartContainerOptions, StartContainerOptions, StopContainerOptions,
+};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::docker::DockerHost;
+use crate::grouping::{COMPOSE_PROJECT_LABEL, COMPOSE_SERVICE_LABEL};
+use crate::types::{AppEvent, EventSender, HostId};
+
+/// Lifecycle action applied to every container of a Compose project or service
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ComposeAction {
+    Restart,
+    Stop,
+    Start,
+}
+
+impl ComposeAction {
+    pub fn verb(self) -> &'static str {
+        match self {
+            ComposeAction::Restart => "Restart",
+            ComposeAction::Stop => "Stop",
+            ComposeAction::Start => "Start",
+        }
+    }
+}
+
+/// A Compose project, or a single service of it, on a host
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComposeTarget {
+    pub host_id: HostId,
+    pub project: String,
+    /// Only the containers (replicas) of this service, or the whole project when None
+    pub service: Option<String>,
+}
+
+impl ComposeTarget {
+    /// Label filters matching the target's containers
+    fn label_filters(&self) -> Vec<String> {
+        let mut labels = vec![format!("{}={}", COMPOSE_PROJECT_LABEL, self.project)];
+        if let Some(service) = &self.service {
+            labels.push(format!("{}={}", COMPOSE_SERVICE_LABEL, service));
+        }
+        labels
+    }
+}
+
+impl fmt::Display for ComposeTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.service {
+            Some(service) => write!(f, "service {} of project {}", service, self.project),
+            None => write!(f, "project {}", self.project),
+        }
+    }
+}
+
+/// Progress of the action on a single container
+#[derive(Clone, Debug, PartialEq)]
+pub enum StepStatus {
+    Pending,
+    Running,
+    Done,
+    Failed(String),
+}
+
+/// A container the action is applied to
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComposeStep {
+    pub id: String,
+    pub name: String,
+    pub status: StepStatus,
+}
+
+/// Update from a running Compose action
+#[derive(Clone, Debug, PartialEq)]
+pub enum ComposeProgress {
+    /// Containers matched by the target, in the order they are processed
+    Containers(Vec<ComposeStep>),
+    /// Status change of the container at an index
+    Step(usize, StepStatus),
+    /// The target's containers couldn't be listed
+    Failed(String),
+    Finished,
+}
+
+/// A Compose action and the progress of each of its containers, as shown in the container list
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComposeOperation {
+    pub target: ComposeTarget,
+    pub action: ComposeAction,
+    pub steps: Vec<ComposeStep>,
+    pub error: Option<String>,
+    pub finished: bool,
+}
+
+impl ComposeOperation {
+    pub fn new(target: ComposeTarget, action: ComposeAction) -> Self {
+        Self {
+            target,
+            action,
+            steps: Vec::new(),
+            error: None,
+            finished: false,
+        }
+    }
+
+    pub fn apply(&mut self, progress: ComposeProgress) {
+        match progress {
+            ComposeProgress::Containers(steps) => self.steps = steps,
+            ComposeProgress::Step(idx, status) => {
+                if let Some(step) = self.steps.get_mut(idx) {
+                    step.status = status;
+                }
+            }
+            ComposeProgress::Failed(error) => {
+                self.error = Some(error);
+                self.finished = true;
+            }
+            ComposeProgress::Finished => self.finished = true,
+        }
+    }
+
+    /// Number of containers the action failed on
+    pub fn failures(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| matches!(step.status, StepStatus::Failed(_)))
+            .count()
+    }
+}
+
+/// Applies an action to every container of a Compose project or service, one at a time
+///
+/// Stopped containers are included so a project can be started again.
+/// Sends the matched containers first, then the status of each container as it changes.
+///
+/// # Arguments
+/// * `host` - Docker host the project runs on
+/// * `target` - Project or service whose containers are affected
+/// * `action` - Action to apply to each container
+/// * `tx` - Event sender channel
+pub async fn run_compose_action(
+    host: DockerHost,
+    target: ComposeTarget,
+    action: ComposeAction,
+    tx: EventSender,
+) {
+    let send = |progress| tx.send(AppEvent::ComposeProgress(progress));
+
+    let options = ListContainersOptions {
+        all: true,
+        filters: Some(HashMap::from([(
+            "label".to_string(),
+            target.label_filters(),
+        )])),
+        ..Default::default()
+    };
+
+    let containers = match host.docker.list_containers(Some(options)).await {
+        Ok(containers) => containers,
+        Err(e) => {
+            let _ = send(ComposeProgress::Failed(e.to_string())).await;
+            return;
+        }
+    };
+
+    let mut steps: Vec<ComposeStep> = containers
+        .iter()
+        .map(|container| ComposeStep {
+            id: container.id.clone().unwrap_or_default(),
+            name: container
+                .names
+                .as_ref()
+                .and_then(|n| n.first().map(|s| s.trim_start_matches('/').to_string()))
+                .unwrap_or_default(),
+            status: StepStatus::Pending,
+        })
+        .collect();
+    steps.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let ids: Vec<String> = steps.iter().map(|step| step.id.clone()).collect();
+    let _ = send(ComposeProgress::Containers(steps)).await;
+
+    for (idx, id) in ids.iter().enumerate() {
+        let _ = send(ComposeProgress::Step(idx, StepStatus::Running)).await;
+
+        let result = match action {
+            ComposeAction::Restart => {
+                host.docker
+                    .restart_container(id, None::<RestartContainerOptions>)
+                    .await
+            }
+            ComposeAction::Stop => {
+                host.docker
+                    .stop_container(id, None::<StopContainerOptions>)
+                    .await
+            }
+            ComposeAction::Start => {
+                host.docker
+                    .start_container(id, None::<StartContainerOptions>)
+                    .await
+            }
+        };
+
+        let status = match result {
+            Ok(()) => StepStatus::Done,
+            Err(e) => StepStatus::Failed(e.to_string()),
+        };
+        let _ = send(ComposeProgress::Step(idx, status)).await;
+    }
+
+    let _ = send(ComposeProgress::Finished).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(service: Option<&str>) -> ComposeTarget {
+        ComposeTarget {
+            host_id: "local".to_string(),
+            project: "shop".to_string(),
+            service: service.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_label_filters() {
+        assert_eq!(
+            target(None).label_filters(),
+            vec!["com.docker.compose.project=shop"]
+        );
+        assert_eq!(
+            target(Some("web")).label_filters(),
+            vec![
+                "com.docker.compose.project=shop",
+                "com.docker.compose.service=web"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_operation_tracks_progress() {
+        let mut operation = ComposeOperation::new(target(Some("web")), ComposeAction::Restart);
+        let step = |name: &str| ComposeStep {
+            id: name.to_string(),
+            name: name.to_string(),
+            status: StepStatus::Pending,
+        };
+
+        operation.apply(ComposeProgress::Containers(vec![
+            step("web-1"),
+            step("web-2"),
+        ]));
+        operation.apply(ComposeProgress::Step(0, StepStatus::Done));
+        operation.apply(ComposeProgress::Step(
+            1,
+            StepStatus::Failed("boom".to_string()),
+        ));
+        // Out of range updates are ignored
+        operation.apply(ComposeProgress::Step(5, StepStatus::Done));
+        assert!(!operation.finished);
+
+        operation.apply(ComposeProgress::Finished);
+
+        assert!(operation.finished);
+        assert_eq!(operation.steps[0].status, StepStatus::Done);
+        assert_eq!(operation.failures(), 1);
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::history::DEFAULT_HISTORY_LENGTH;
+use crate::smoothing::SmoothingConfig;
+
 /// Configuration for a single Docker host
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct HostConfig {
@@ -21,6 +24,27 @@ pub struct Config {
     /// Docker host(s) to connect to
     #[serde(default)]
     pub hosts: Vec<HostConfig>,
+
+    /// Command used to open an interactive shell in a container (defaults to "/bin/sh")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+
+    /// Key sequence that detaches from an attached container (defaults to "ctrl-p,ctrl-q")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detach_keys: Option<String>,
+
+    /// Number of stats samples kept per container for sparklines and charts
+    /// (defaults to DEFAULT_HISTORY_LENGTH, enough for the longest chart window)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_length: Option<usize>,
+
+    /// Label identifying the same service across hosts in the fleet view (defaults to the container name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fleet_label: Option<String>,
+
+    /// Smoothing applied to each metric's stats (defaults to an EMA with alpha 0.3)
+    #[serde(default)]
+    pub smoothing: SmoothingConfig,
 }
 
 impl Config {
@@ -85,6 +109,35 @@ impl Config {
     pub fn host_strings(&self) -> Vec<&str> {
         self.hosts.iter().map(|h| h.host.as_str()).collect()
     }
+
+    /// Get the shell command split into arguments for the exec API
+    pub fn shell_command(&self) -> Vec<String> {
+        let command: Vec<String> = self
+            .shell
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(|s| s.to_string())
+            .collect();
+
+        if command.is_empty() {
+            vec!["/bin/sh".to_string()]
+        } else {
+            command
+        }
+    }
+
+    /// Get the detach key sequence used when attaching to a container
+    pub fn detach_keys(&self) -> &str {
+        self.detach_keys.as_deref().unwrap_or("ctrl-p,ctrl-q")
+    }
+
+    /// Get the number of stats samples kept per container
+    pub fn history_length(&self) -> usize {
+        self.history_length
+            .filter(|&length| length > 0)
+            .unwrap_or(DEFAULT_HISTORY_LENGTH)
+    }
 }
 
 #[cfg(test)]
@@ -104,6 +157,7 @@ mod tests {
                 host: "ssh://user@server1".to_string(),
                 dozzle: None,
             }],
+            ..Default::default()
         };
 
         let merged = config.merge_with_cli_hosts(vec!["ssh://user@server2".to_string()], false);
@@ -117,6 +171,7 @@ mod tests {
                 host: "ssh://user@server1".to_string(),
                 dozzle: Some("https://dozzle.example.com".to_string()),
             }],
+            ..Default::default()
         };
 
         let merged = config.merge_with_cli_hosts(vec!["local".to_string()], true);
@@ -130,7 +185,7 @@ mod tests {
 
     #[test]
     fn test_merge_with_cli_hosts_defaults_to_local() {
-        let config = Config { hosts: vec![] };
+        let config = Config::default();
 
         let merged = config.merge_with_cli_hosts(vec!["local".to_string()], true);
         assert_eq!(merged.host_strings(), vec!["local"]);
@@ -174,6 +229,40 @@ hosts:
         assert_eq!(config.hosts[1].dozzle, None);
     }
 
+    #[test]
+    fn test_shell_command_defaults_to_sh() {
+        let config = Config::default();
+        assert_eq!(config.shell_command(), vec!["/bin/sh"]);
+
+        let blank = Config {
+            shell: Some("   ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(blank.shell_command(), vec!["/bin/sh"]);
+    }
+
+    #[test]
+    fn test_yaml_deserialization_with_shell() {
+        let yaml = r#"
+hosts:
+  - host: local
+shell: /bin/bash -l
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.shell_command(), vec!["/bin/bash", "-l"]);
+    }
+
+    #[test]
+    fn test_detach_keys_default_and_override() {
+        assert_eq!(Config::default().detach_keys(), "ctrl-p,ctrl-q");
+
+        let yaml = r#"
+detach_keys: ctrl-a,d
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.detach_keys(), "ctrl-a,d");
+    }
+
     #[test]
     fn test_host_config_without_dozzle() {
         let host = HostConfig {
@@ -193,4 +282,15 @@ hosts:
         assert_eq!(host.host, "ssh://user@host");
         assert_eq!(host.dozzle.as_deref(), Some("https://dozzle.example.com"));
     }
+
+    #[test]
+    fn test_history_length() {
+        assert_eq!(Config::default().history_length(), DEFAULT_HISTORY_LENGTH);
+
+        let config: Config = serde_yaml::from_str("history_length: 60").unwrap();
+        assert_eq!(config.history_length(), 60);
+
+        let config: Config = serde_yaml::from_str("history_length: 0").unwrap();
+        assert_eq!(config.history_length(), DEFAULT_HISTORY_LENGTH);
+    }
 }
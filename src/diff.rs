@@ -0,0 +1,244 @@
+use bollard::models::{ChangeType, FilesystemChange};
+use bollard::query_parameters::InspectContainerOptions;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::docker::DockerHost;
+use crate::types::{AppEvent, ContainerKey, EventSender};
+
+/// How a path differs from the container's image
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+impl ChangeKind {
+    /// Single-letter marker used by `docker diff`
+    pub fn marker(self) -> &'static str {
+        match self {
+            ChangeKind::Added => "A",
+            ChangeKind::Modified => "C",
+            ChangeKind::Deleted => "D",
+        }
+    }
+}
+
+impl From<ChangeType> for ChangeKind {
+    fn from(kind: ChangeType) -> Self {
+        match kind {
+            ChangeType::_0 => ChangeKind::Modified,
+            ChangeType::_1 => ChangeKind::Added,
+            ChangeType::_2 => ChangeKind::Deleted,
+        }
+    }
+}
+
+/// A single changed path in the container's writable layer
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathChange {
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+/// Changed paths that share a parent directory
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeGroup {
+    pub directory: String,
+    pub added: usize,
+    pub modified: usize,
+    pub deleted: usize,
+    pub changes: Vec<PathChange>,
+}
+
+/// Filesystem changes of a container, as shown in the changes view
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContainerChanges {
+    pub groups: Vec<ChangeGroup>,
+    /// Size of the writable layer in bytes, if the daemon reported it
+    pub size_rw: Option<i64>,
+}
+
+impl ContainerChanges {
+    /// Total number of changed paths across all groups
+    pub fn total(&self) -> usize {
+        self.groups.iter().map(|g| g.changes.len()).sum()
+    }
+
+    /// Number of rows in the changes view - one per group header plus one per path
+    pub fn row_count(&self) -> usize {
+        self.groups.len() + self.total()
+    }
+}
+
+/// Fetches the changed paths and writable layer size of a container
+///
+/// # Arguments
+/// * `host` - Docker host instance with identifier
+/// * `container_id` - Container ID (truncated IDs are accepted by the Docker API)
+/// * `tx` - Event sender channel
+pub async fn fetch_container_changes(host: DockerHost, container_id: String, tx: EventSender) {
+    let key = ContainerKey::new(host.host_id.clone(), container_id.clone());
+
+    let result = match host.docker.container_changes(&container_id).await {
+        Ok(changes) => {
+            // Size calculation can be slow, so a failure here only hides the size
+            let size_rw = host
+                .docker
+                .inspect_container(&container_id, Some(InspectContainerOptions { size: true }))
+                .await
+                .ok()
+                .and_then(|inspect| inspect.size_rw);
+
+            Ok(ContainerChanges {
+                groups: group_changes(changes.unwrap_or_default()),
+                size_rw,
+            })
+        }
+        Err(e) => Err(e.to_string()),
+    };
+
+    let _ = tx.send(AppEvent::ContainerChanges(key, result)).await;
+}
+
+/// Groups changed paths by their parent directory, sorted by directory and path
+///
+/// The changes API reports every changed ancestor directory as modified too,
+/// so directories that only appear because of changes below them are dropped
+pub fn group_changes(changes: Vec<FilesystemChange>) -> Vec<ChangeGroup> {
+    let ancestors = ancestor_directories(changes.iter().map(|c| c.path.as_str()));
+    let is_parent_only = |change: &FilesystemChange| {
+        ChangeKind::from(change.kind) == ChangeKind::Modified
+            && ancestors.contains(change.path.as_str())
+    };
+
+    let mut groups: BTreeMap<String, ChangeGroup> = BTreeMap::new();
+
+    for change in changes.iter().filter(|c| !is_parent_only(c)) {
+        let directory = parent_directory(&change.path);
+        let kind = ChangeKind::from(change.kind);
+
+        let group = groups
+            .entry(directory.clone())
+            .or_insert_with(|| ChangeGroup {
+                directory,
+                added: 0,
+                modified: 0,
+                deleted: 0,
+                changes: Vec::new(),
+            });
+
+        match kind {
+            ChangeKind::Added => group.added += 1,
+            ChangeKind::Modified => group.modified += 1,
+            ChangeKind::Deleted => group.deleted += 1,
+        }
+
+        group.changes.push(PathChange {
+            path: change.path.clone(),
+            kind,
+        });
+    }
+
+    groups
+        .into_values()
+        .map(|mut group| {
+            group.changes.sort_by(|a, b| a.path.cmp(&b.path));
+            group
+        })
+        .collect()
+}
+
+/// Collects every directory that contains at least one of the paths
+fn ancestor_directories<'a>(paths: impl Iterator<Item = &'a str>) -> HashSet<&'a str> {
+    let mut ancestors = HashSet::new();
+
+    for path in paths {
+        let mut current = path.trim_end_matches('/');
+        while let Some(idx) = current.rfind('/') {
+            current = if idx == 0 { "/" } else { &current[..idx] };
+            // Ancestors of a known ancestor have been collected already
+            if !ancestors.insert(current) || current == "/" {
+                break;
+            }
+        }
+    }
+
+    ancestors
+}
+
+/// Returns the directory containing a path, "/" for top-level entries
+fn parent_directory(path: &str) -> String {
+    match path.trim_end_matches('/').rsplit_once('/') {
+        Some((parent, _)) if !parent.is_empty() => parent.to_string(),
+        _ => "/".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(path: &str, kind: ChangeType) -> FilesystemChange {
+        FilesystemChange {
+            path: path.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_group_changes_by_directory() {
+        let groups = group_changes(vec![
+            change("/var", ChangeType::_0),
+            change("/var/log", ChangeType::_0),
+            change("/var/log/app.log", ChangeType::_1),
+            change("/var/log/old.log", ChangeType::_2),
+            change("/tmp", ChangeType::_0),
+            change("/tmp/cache", ChangeType::_1),
+            change("/etc/hosts", ChangeType::_0),
+        ]);
+
+        let summary: Vec<_> = groups
+            .iter()
+            .map(|g| (g.directory.as_str(), g.added, g.modified, g.deleted))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("/etc", 0, 1, 0), ("/tmp", 1, 0, 0), ("/var/log", 1, 0, 1)]
+        );
+        assert_eq!(groups[2].changes[1].path, "/var/log/old.log");
+        assert_eq!(groups[2].changes[1].kind, ChangeKind::Deleted);
+    }
+
+    #[test]
+    fn test_group_changes_keeps_modified_leaf_directories() {
+        // Ancestors of other changes are dropped, a directory changed on its own is kept
+        let groups = group_changes(vec![
+            change("/data", ChangeType::_0),
+            change("/data/new", ChangeType::_1),
+            change("/root", ChangeType::_0),
+        ]);
+
+        let directories: Vec<_> = groups.iter().map(|g| g.directory.as_str()).collect();
+        assert_eq!(directories, vec!["/", "/data"]);
+        assert_eq!(groups[0].changes[0].path, "/root");
+        assert_eq!(groups[0].modified, 1);
+        assert_eq!(groups[1].added, 1);
+    }
+
+    #[test]
+    fn test_parent_directory() {
+        assert_eq!(parent_directory("/etc/hosts"), "/etc");
+        assert_eq!(parent_directory("/etc"), "/");
+        assert_eq!(parent_directory("/a/b/c/"), "/a/b");
+    }
+
+    #[test]
+    fn test_ancestor_directories() {
+        let ancestors =
+            ancestor_directories(["/var/log/app.log", "/variable", "/var/lib/"].into_iter());
+
+        assert_eq!(ancestors, HashSet::from(["/", "/var", "/var/log"]));
+        assert!(!ancestors.contains("/variable"));
+    }
+}
@@ -4,8 +4,14 @@ use futures_util::stream::StreamExt;
 use std::collections::HashMap;
 use std::time::Duration;
 
+use crate::events::{CONTAINER_EVENT_ACTIONS, ContainerEvent};
+use crate::health::Health;
+use crate::host::poll_host_info;
+use crate::smoothing::SmoothingReceiver;
 use crate::stats::stream_container_stats;
-use crate::types::{AppEvent, Container, ContainerKey, ContainerStats, EventSender, HostId};
+use crate::types::{
+    AppEvent, Container, ContainerKey, ContainerStats, EventSender, HostId, Lifecycle,
+};
 
 /// Represents a Docker host connection with its identifier
 #[derive(Clone)]
@@ -21,20 +27,24 @@ impl DockerHost {
 }
 
 /// Manages container monitoring for a specific Docker host: fetches initial containers and listens for Docker events
-pub async fn container_manager(host: DockerHost, tx: EventSender) {
+pub async fn container_manager(host: DockerHost, tx: EventSender, smoothing: SmoothingReceiver) {
     let mut active_containers: HashMap<String, tokio::task::JoinHandle<()>> = HashMap::new();
 
+    // Keep the host's capacity and container counts up to date for the summary header
+    tokio::spawn(poll_host_info(host.clone(), tx.clone()));
+
     // Fetch and start monitoring initial containers
-    fetch_initial_containers(&host, &tx, &mut active_containers).await;
+    fetch_initial_containers(&host, &tx, &smoothing, &mut active_containers).await;
 
     // Subscribe to Docker events and handle container lifecycle
-    monitor_docker_events(&host, &tx, &mut active_containers).await;
+    monitor_docker_events(&host, &tx, &smoothing, &mut active_containers).await;
 }
 
 /// Fetches the initial list of running containers and starts monitoring them
 async fn fetch_initial_containers(
     host: &DockerHost,
     tx: &EventSender,
+    smoothing: &SmoothingReceiver,
     active_containers: &mut HashMap<String, tokio::task::JoinHandle<()>>,
 ) {
     let list_options = Some(ListContainersOptions {
@@ -53,19 +63,32 @@ async fn fetch_initial_containers(
                 .as_ref()
                 .and_then(|n| n.first().map(|s| s.trim_start_matches('/').to_string()))
                 .unwrap_or_default();
-            let status = container.status.clone().unwrap_or_default();
+            // The plain state, like the events stream reports; the summary text
+            // ("Up 2 hours (healthy)") only carries the health check status
+            let status = container
+                .state
+                .as_ref()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| "running".to_string());
+            let health = container
+                .status
+                .as_deref()
+                .map_or(Health::None, Health::from_list_status);
 
             let container_info = Container {
                 id: truncated_id.clone(),
                 name: name.clone(),
-                status: status.clone(),
+                status,
                 stats: ContainerStats::default(),
                 host_id: host.host_id.clone(),
+                labels: container.labels.clone().unwrap_or_default(),
+                health,
+                lifecycle: Lifecycle::default(),
             };
 
             initial_containers.push(container_info);
 
-            start_container_monitoring(host, &truncated_id, tx, active_containers);
+            start_container_monitoring(host, &truncated_id, tx, smoothing, active_containers);
         }
 
         // Send all initial containers in one event
@@ -80,17 +103,21 @@ async fn fetch_initial_containers(
     }
 }
 
-/// Monitors Docker events for container start/stop/die events
+/// Monitors Docker events for container lifecycle changes
 async fn monitor_docker_events(
     host: &DockerHost,
     tx: &EventSender,
+    smoothing: &SmoothingReceiver,
     active_containers: &mut HashMap<String, tokio::task::JoinHandle<()>>,
 ) {
     let mut filters = HashMap::new();
     filters.insert("type".to_string(), vec!["container".to_string()]);
     filters.insert(
         "event".to_string(),
-        vec!["start".to_string(), "die".to_string(), "stop".to_string()],
+        CONTAINER_EVENT_ACTIONS
+            .iter()
+            .map(|action| action.to_string())
+            .collect(),
     );
 
     let events_options = EventsOptions {
@@ -106,17 +133,41 @@ async fn monitor_docker_events(
                 if let Some(actor) = event.actor {
                     let container_id = actor.id.unwrap_or_default();
                     let action = event.action.unwrap_or_default();
+                    let attributes = actor.attributes.unwrap_or_default();
 
-                    match action.as_str() {
-                        "start" => {
-                            handle_container_start(host, &container_id, tx, active_containers)
-                                .await;
+                    let Some(container_event) = ContainerEvent::parse(&action, &attributes) else {
+                        continue;
+                    };
+
+                    let truncated_id = container_id[..12.min(container_id.len())].to_string();
+                    let key = ContainerKey::new(host.host_id.clone(), truncated_id);
+
+                    let app_event = match container_event {
+                        ContainerEvent::Start => {
+                            handle_container_start(
+                                host,
+                                &container_id,
+                                tx,
+                                smoothing,
+                                active_containers,
+                            )
+                            .await;
+                            continue;
                         }
-                        "die" | "stop" => {
+                        ContainerEvent::Stop | ContainerEvent::Destroy => {
                             handle_container_stop(host, &container_id, tx, active_containers).await;
+                            continue;
                         }
-                        _ => {}
-                    }
+                        ContainerEvent::Pause => AppEvent::ContainerPaused(key),
+                        ContainerEvent::Unpause => AppEvent::ContainerUnpaused(key),
+                        ContainerEvent::Restart => AppEvent::ContainerRestarted(key),
+                        ContainerEvent::OutOfMemory => AppEvent::ContainerOutOfMemory(key),
+                        ContainerEvent::Kill { signal } => AppEvent::ContainerKilled(key, signal),
+                        ContainerEvent::Rename { name } => AppEvent::ContainerRenamed(key, name),
+                        ContainerEvent::Health(health) => AppEvent::ContainerHealth(key, health),
+                    };
+
+                    let _ = tx.send(app_event).await;
                 }
             }
             Err(_) => {
@@ -133,19 +184,22 @@ async fn monitor_docker_events(
 /// * `host` - Docker host instance with identifier
 /// * `truncated_id` - Truncated container ID (12 chars)
 /// * `tx` - Event sender channel
+/// * `smoothing` - Receiver of the smoothing applied to the stats
 /// * `active_containers` - Map of active container monitoring tasks
 fn start_container_monitoring(
     host: &DockerHost,
     truncated_id: &str,
     tx: &EventSender,
+    smoothing: &SmoothingReceiver,
     active_containers: &mut HashMap<String, tokio::task::JoinHandle<()>>,
 ) {
     let tx_clone = tx.clone();
     let host_clone = host.clone();
     let truncated_id_clone = truncated_id.to_string();
+    let smoothing_clone = smoothing.clone();
 
     let handle = tokio::spawn(async move {
-        stream_container_stats(host_clone, truncated_id_clone, tx_clone).await;
+        stream_container_stats(host_clone, truncated_id_clone, tx_clone, smoothing_clone).await;
     });
 
     active_containers.insert(truncated_id.to_string(), handle);
@@ -156,6 +210,7 @@ async fn handle_container_start(
     host: &DockerHost,
     container_id: &str,
     tx: &EventSender,
+    smoothing: &SmoothingReceiver,
     active_containers: &mut HashMap<String, tokio::task::JoinHandle<()>>,
 ) {
     let truncated_id = container_id[..12.min(container_id.len())].to_string();
@@ -176,9 +231,22 @@ async fn handle_container_start(
             .state
             .as_ref()
             .and_then(|s| s.status.as_ref())
-            .map(|s| format!("{:?}", s))
+            .map(|s| s.to_string())
             .unwrap_or_else(|| "running".to_string());
 
+        let health = inspect
+            .state
+            .as_ref()
+            .and_then(|s| s.health.as_ref())
+            .and_then(|h| h.status.as_ref())
+            .map_or(Health::None, |status| Health::from_status(status.as_ref()));
+
+        let labels = inspect
+            .config
+            .as_ref()
+            .and_then(|config| config.labels.clone())
+            .unwrap_or_default();
+
         // Start monitoring the new container
         if !active_containers.contains_key(&truncated_id) {
             let container = Container {
@@ -187,11 +255,14 @@ async fn handle_container_start(
                 status: status.clone(),
                 stats: ContainerStats::default(),
                 host_id: host.host_id.clone(),
+                labels,
+                health,
+                lifecycle: Lifecycle::default(),
             };
 
             let _ = tx.send(AppEvent::ContainerCreated(container)).await;
 
-            start_container_monitoring(host, &truncated_id, tx, active_containers);
+            start_container_monitoring(host, &truncated_id, tx, smoothing, active_containers);
         }
     }
 }
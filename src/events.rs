@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use crate::health::Health;
+
+/// Container event actions subscribed to on every host
+pub const CONTAINER_EVENT_ACTIONS: &[&str] = &[
+    "start",
+    "die",
+    "stop",
+    "destroy",
+    "pause",
+    "unpause",
+    "restart",
+    "oom",
+    "kill",
+    "rename",
+    "health_status",
+];
+
+/// A container lifecycle change reported by the Docker events stream
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContainerEvent {
+    Start,
+    /// The container stopped or exited ("die" and "stop")
+    Stop,
+    Destroy,
+    Pause,
+    Unpause,
+    Restart,
+    /// A process in the container was killed for running out of memory
+    OutOfMemory,
+    /// A signal was sent to the container, which may or may not exit because of it
+    Kill {
+        signal: String,
+    },
+    Rename {
+        name: String,
+    },
+    Health(Health),
+}
+
+impl ContainerEvent {
+    /// Parses an event's action and actor attributes, None for actions that aren't tracked
+    pub fn parse(action: &str, attributes: &HashMap<String, String>) -> Option<Self> {
+        let attribute = |name: &str| attributes.get(name).cloned().unwrap_or_default();
+
+        let event = match action {
+            "start" => ContainerEvent::Start,
+            "die" | "stop" => ContainerEvent::Stop,
+            "destroy" => ContainerEvent::Destroy,
+            "pause" => ContainerEvent::Pause,
+            "unpause" => ContainerEvent::Unpause,
+            "restart" => ContainerEvent::Restart,
+            "oom" => ContainerEvent::OutOfMemory,
+            "kill" => ContainerEvent::Kill {
+                signal: attribute("signal"),
+            },
+            "rename" => ContainerEvent::Rename {
+                name: attribute("name").trim_start_matches('/').to_string(),
+            },
+            // Health events carry the new status in the action: "health_status: healthy"
+            action if action.starts_with("health_status") => {
+                let status = action.split_once(':').map_or("", |(_, status)| status);
+                ContainerEvent::Health(Health::from_status(status))
+            }
+            _ => return None,
+        };
+
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(action: &str, attributes: &[(&str, &str)]) -> Option<ContainerEvent> {
+        let attributes = attributes
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        ContainerEvent::parse(action, &attributes)
+    }
+
+    #[test]
+    fn test_parse_lifecycle_actions() {
+        assert_eq!(parse("die", &[]), Some(ContainerEvent::Stop));
+        assert_eq!(parse("pause", &[]), Some(ContainerEvent::Pause));
+        assert_eq!(parse("oom", &[]), Some(ContainerEvent::OutOfMemory));
+        assert_eq!(parse("exec_start: sh", &[]), None);
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        assert_eq!(
+            parse("kill", &[("signal", "15")]),
+            Some(ContainerEvent::Kill {
+                signal: "15".to_string()
+            })
+        );
+        assert_eq!(
+            parse("rename", &[("name", "web"), ("oldName", "/old-web")]),
+            Some(ContainerEvent::Rename {
+                name: "web".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_health_status() {
+        assert_eq!(
+            parse("health_status: unhealthy", &[]),
+            Some(ContainerEvent::Health(Health::Unhealthy))
+        );
+    }
+}
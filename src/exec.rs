@@ -0,0 +1,230 @@
+use bollard::container::LogOutput;
+use bollard::exec::{CreateExecOptions, StartExecOptions, StartExecResults};
+use futures_util::stream::StreamExt;
+use std::time::{Duration, Instant};
+
+use crate::docker::DockerHost;
+use crate::types::{AppEvent, ContainerKey, EventSender};
+
+/// Maximum number of commands remembered per container
+pub const EXEC_HISTORY_LIMIT: usize = 20;
+
+/// Output stream an exec line was written to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExecStream {
+    Stdout,
+    Stderr,
+}
+
+/// A single line of output from an exec command
+#[derive(Clone, Debug)]
+pub struct ExecLine {
+    pub stream: ExecStream,
+    pub text: String,
+}
+
+/// Final outcome of an exec command
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExecStatus {
+    /// Command ran to completion
+    Exited {
+        exit_code: Option<i64>,
+        duration: Duration,
+    },
+    /// Command could not be created or started
+    Failed(String),
+}
+
+/// A command that was run (or is running) and its collected output
+#[derive(Clone, Debug)]
+pub struct ExecSession {
+    pub key: ContainerKey,
+    pub command: String,
+    pub lines: Vec<ExecLine>,
+    pub started_at: Instant,
+    /// None while the command is still running
+    pub status: Option<ExecStatus>,
+}
+
+impl ExecSession {
+    pub fn new(key: ContainerKey, command: String) -> Self {
+        Self {
+            key,
+            command,
+            lines: Vec::new(),
+            started_at: Instant::now(),
+            status: None,
+        }
+    }
+}
+
+/// Records a command in a container's history, moving repeats to the end
+pub fn push_history(history: &mut Vec<String>, command: &str) {
+    history.retain(|c| c != command);
+    history.push(command.to_string());
+
+    if history.len() > EXEC_HISTORY_LIMIT {
+        let excess = history.len() - EXEC_HISTORY_LIMIT;
+        history.drain(..excess);
+    }
+}
+
+/// Runs a command non-interactively in a container and streams its output
+/// The command is run through `sh -c` so pipes and redirects work as typed
+///
+/// # Arguments
+/// * `host` - Docker host instance the container runs on
+/// * `container_id` - Container ID (truncated IDs are accepted by the Docker API)
+/// * `command` - Command line to execute
+/// * `tx` - Event sender channel
+pub async fn run_exec_command(
+    host: DockerHost,
+    container_id: String,
+    command: String,
+    tx: EventSender,
+) {
+    let key = ContainerKey::new(host.host_id.clone(), container_id.clone());
+    let started_at = Instant::now();
+
+    let status = match stream_exec_output(&host, &container_id, command, &key, &tx).await {
+        Ok(exit_code) => ExecStatus::Exited {
+            exit_code,
+            duration: started_at.elapsed(),
+        },
+        Err(e) => ExecStatus::Failed(e.to_string()),
+    };
+
+    let _ = tx.send(AppEvent::ExecFinished(key, status)).await;
+}
+
+/// Creates and starts the exec instance, forwarding output lines until the command exits
+/// Returns the command's exit code
+async fn stream_exec_output(
+    host: &DockerHost,
+    container_id: &str,
+    command: String,
+    key: &ContainerKey,
+    tx: &EventSender,
+) -> Result<Option<i64>, bollard::errors::Error> {
+    let exec = host
+        .docker
+        .create_exec(
+            container_id,
+            CreateExecOptions {
+                attach_stdout: Some(true),
+                attach_stderr: Some(true),
+                cmd: Some(vec!["sh".to_string(), "-c".to_string(), command]),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    if let StartExecResults::Attached { mut output, .. } = host
+        .docker
+        .start_exec(&exec.id, None::<StartExecOptions>)
+        .await?
+    {
+        // Chunks aren't line-aligned, so keep a partial line buffer per stream
+        let mut stdout_buffer = String::new();
+        let mut stderr_buffer = String::new();
+
+        while let Some(chunk) = output.next().await {
+            let (stream, buffer, message) = match chunk? {
+                LogOutput::StdErr { message } => (ExecStream::Stderr, &mut stderr_buffer, message),
+                LogOutput::StdOut { message } | LogOutput::Console { message } => {
+                    (ExecStream::Stdout, &mut stdout_buffer, message)
+                }
+                LogOutput::StdIn { .. } => continue,
+            };
+
+            for text in split_lines(buffer, &String::from_utf8_lossy(&message)) {
+                let line = ExecLine { stream, text };
+                if tx
+                    .send(AppEvent::ExecOutput(key.clone(), line))
+                    .await
+                    .is_err()
+                {
+                    return Ok(None);
+                }
+            }
+        }
+
+        // Flush output that didn't end with a newline
+        for (stream, buffer) in [
+            (ExecStream::Stdout, stdout_buffer),
+            (ExecStream::Stderr, stderr_buffer),
+        ] {
+            if !buffer.is_empty() {
+                let line = ExecLine {
+                    stream,
+                    text: buffer,
+                };
+                let _ = tx.send(AppEvent::ExecOutput(key.clone(), line)).await;
+            }
+        }
+    }
+
+    let inspect = host.docker.inspect_exec(&exec.id).await?;
+    Ok(inspect.exit_code)
+}
+
+/// Appends a chunk to the partial line buffer and returns all completed lines
+fn split_lines(buffer: &mut String, chunk: &str) -> Vec<String> {
+    buffer.push_str(chunk);
+
+    let mut lines = Vec::new();
+    while let Some(idx) = buffer.find('\n') {
+        let line: String = buffer.drain(..=idx).collect();
+        lines.push(line.trim_end_matches(['\n', '\r']).to_string());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(commands: &[&str]) -> Vec<String> {
+        commands.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_split_lines_complete_and_partial() {
+        let mut buffer = String::new();
+
+        assert_eq!(
+            split_lines(&mut buffer, "one\ntwo\nthr"),
+            vec!["one", "two"]
+        );
+        assert_eq!(buffer, "thr");
+
+        assert_eq!(split_lines(&mut buffer, "ee\r\n"), vec!["three"]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_split_lines_no_newline() {
+        let mut buffer = String::new();
+
+        assert!(split_lines(&mut buffer, "partial").is_empty());
+        assert_eq!(buffer, "partial");
+    }
+
+    #[test]
+    fn test_push_history_moves_repeats_to_end() {
+        let mut commands = history(&["env", "ls", "cat /etc/hosts"]);
+        push_history(&mut commands, "env");
+        assert_eq!(commands, history(&["ls", "cat /etc/hosts", "env"]));
+    }
+
+    #[test]
+    fn test_push_history_is_capped() {
+        let mut commands = Vec::new();
+        for i in 0..EXEC_HISTORY_LIMIT + 5 {
+            push_history(&mut commands, &format!("echo {}", i));
+        }
+
+        assert_eq!(commands.len(), EXEC_HISTORY_LIMIT);
+        assert_eq!(commands[0], "echo 5");
+    }
+}
@@ -0,0 +1,849 @@
+use bollard::container::LogOutput;
+use bollard::errors::Error as DockerError;
+use bollard::exec::{CreateExecOptions, StartExecOptions, StartExecResults};
+use bollard::query_parameters::DownloadFromContainerOptions;
+use futures_util::stream::{Stream, StreamExt};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::AsyncWriteExt;
+
+use crate::docker::DockerHost;
+use crate::types::{AppEvent, ContainerKey, EventSender};
+
+/// Maximum archive bytes read while listing a directory without `ls`
+/// The archive API always returns directories recursively, so large trees are cut off here
+const LIST_BYTE_LIMIT: usize = 32 * 1024 * 1024;
+
+/// Maximum number of entries listed in a directory
+const LIST_ENTRY_LIMIT: usize = 10_000;
+
+/// Maximum bytes of a file shown in the preview pane
+const PREVIEW_BYTE_LIMIT: usize = 64 * 1024;
+
+/// Archive bytes read to find out what a path is - its header and the preview of a file
+const HEAD_BYTE_LIMIT: usize = PREVIEW_BYTE_LIMIT + 16 * 1024;
+
+/// Symbolic links followed for one path before giving up, the same limit as the kernel
+pub const MAX_LINK_HOPS: usize = 40;
+
+/// Type of a filesystem entry inside a container
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileKind {
+    Directory,
+    File,
+    /// Symbolic link with its target
+    Symlink(String),
+    /// Devices, FIFOs and other special files
+    Other,
+}
+
+/// A single entry in a container directory listing
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileEntry {
+    pub name: String,
+    pub kind: FileKind,
+    pub size: u64,
+}
+
+/// Beginning of a file's contents for the preview pane
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilePreview {
+    pub text: String,
+    /// Total file size in bytes
+    pub size: u64,
+    /// Whether the file is larger than the preview
+    pub truncated: bool,
+    /// Whether the file looks like binary data (text is empty)
+    pub binary: bool,
+}
+
+/// What a container path turned out to be when fetched through the archive API
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathContents {
+    Directory {
+        entries: Vec<FileEntry>,
+        /// Whether the listing was cut off at the entry or byte limit
+        truncated: bool,
+    },
+    File(FilePreview),
+    /// Symbolic link with its target
+    Symlink(String),
+}
+
+/// File browser state for the container shown in the file view
+#[derive(Clone, Debug)]
+pub struct FileBrowser {
+    pub key: ContainerKey,
+    /// Directory currently listed
+    pub path: String,
+    /// Entries of the current directory, with ".." first unless at the root
+    pub entries: Vec<FileEntry>,
+    /// Whether the current listing is partial
+    pub truncated: bool,
+    /// Previewed file path and contents
+    pub preview: Option<(String, FilePreview)>,
+    /// Whether a listing or preview request is in flight
+    pub loading: bool,
+    /// Path of the latest request, replies for other paths are stale
+    pub requested_path: Option<String>,
+    /// Symbolic links followed since the user last picked a path
+    pub link_hops: usize,
+    /// Result of the last download
+    pub message: Option<String>,
+    /// Error from the last request
+    pub error: Option<String>,
+}
+
+impl FileBrowser {
+    pub fn new(key: ContainerKey) -> Self {
+        Self {
+            key,
+            path: "/".to_string(),
+            entries: Vec::new(),
+            truncated: false,
+            preview: None,
+            loading: true,
+            requested_path: None,
+            link_hops: 0,
+            message: None,
+            error: None,
+        }
+    }
+
+    /// Replaces the listing with the contents of a directory
+    pub fn set_directory(&mut self, path: String, mut entries: Vec<FileEntry>, truncated: bool) {
+        if path != "/" {
+            entries.insert(
+                0,
+                FileEntry {
+                    name: "..".to_string(),
+                    kind: FileKind::Directory,
+                    size: 0,
+                },
+            );
+        }
+
+        self.path = path;
+        self.entries = entries;
+        self.truncated = truncated;
+        self.preview = None;
+    }
+}
+
+/// Fetches a container path through the archive API and sends what it contains
+///
+/// # Arguments
+/// * `host` - Docker host instance with identifier
+/// * `container_id` - Container ID (truncated IDs are accepted by the Docker API)
+/// * `path` - Absolute path inside the container
+/// * `tx` - Event sender channel
+pub async fn browse_container_path(
+    host: DockerHost,
+    container_id: String,
+    path: String,
+    tx: EventSender,
+) {
+    let key = ContainerKey::new(host.host_id.clone(), container_id.clone());
+    let result = fetch_path_contents(&host, &container_id, &path).await;
+    let _ = tx.send(AppEvent::PathContents(key, path, result)).await;
+}
+
+/// Reads the start of the path's archive to find out what it is, then lists a
+/// directory's direct children with `ls` or previews a file
+///
+/// The archive of a directory contains the whole tree, so reading it would never reach
+/// later children of large directories. It is only used for listings when the container
+/// has no GNU `ls`, up to the byte limit.
+async fn fetch_path_contents(
+    host: &DockerHost,
+    container_id: &str,
+    path: &str,
+) -> Result<PathContents, String> {
+    let options = Some(DownloadFromContainerOptions {
+        path: path.to_string(),
+    });
+    let mut stream = Box::pin(host.docker.download_from_container(container_id, options));
+
+    let mut data = Vec::new();
+    let complete = read_archive(&mut stream, &mut data, HEAD_BYTE_LIMIT).await?;
+    let contents = parse_path_archive(&data, complete)?;
+
+    if !matches!(contents, PathContents::Directory { .. }) {
+        return Ok(contents);
+    }
+
+    if let Ok(listing) = list_directory(host, container_id, path).await {
+        return Ok(listing);
+    }
+
+    // No usable `ls` (e.g. distroless images) - fall back to the recursive archive
+    let complete = complete && read_archive(&mut stream, &mut data, LIST_BYTE_LIMIT).await?;
+    parse_path_archive(&data, complete)
+}
+
+/// Reads archive chunks into `data` until the stream ends or `limit` bytes are buffered
+/// Returns whether the whole archive was read; dropping the stream closes the connection
+async fn read_archive<S, B>(
+    stream: &mut S,
+    data: &mut Vec<u8>,
+    limit: usize,
+) -> Result<bool, String>
+where
+    S: Stream<Item = Result<B, DockerError>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    while data.len() < limit {
+        match stream.next().await {
+            Some(chunk) => data.extend_from_slice(chunk.map_err(|e| e.to_string())?.as_ref()),
+            None => return Ok(true),
+        }
+    }
+    Ok(false)
+}
+
+/// Lists the direct children of a directory by running `ls` in the container
+async fn list_directory(
+    host: &DockerHost,
+    container_id: &str,
+    path: &str,
+) -> Result<PathContents, String> {
+    let exec = host
+        .docker
+        .create_exec(
+            container_id,
+            CreateExecOptions {
+                attach_stdout: Some(true),
+                attach_stderr: Some(true),
+                cmd: Some(vec![
+                    "ls".to_string(),
+                    "-lA".to_string(),
+                    "--time-style=+%s".to_string(),
+                    "--".to_string(),
+                    path.to_string(),
+                ]),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut stdout = Vec::new();
+    if let StartExecResults::Attached { mut output, .. } = host
+        .docker
+        .start_exec(&exec.id, None::<StartExecOptions>)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        while let Some(chunk) = output.next().await {
+            if let LogOutput::StdOut { message } = chunk.map_err(|e| e.to_string())? {
+                stdout.extend_from_slice(&message);
+            }
+        }
+    }
+
+    let inspect = host
+        .docker
+        .inspect_exec(&exec.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if inspect.exit_code != Some(0) {
+        return Err("ls failed".to_string());
+    }
+
+    Ok(parse_ls_output(&String::from_utf8_lossy(&stdout)))
+}
+
+/// Parses `ls -lA --time-style=+%s` output into a listing, keeping at most LIST_ENTRY_LIMIT entries
+pub fn parse_ls_output(output: &str) -> PathContents {
+    let mut entries: Vec<FileEntry> = output
+        .lines()
+        .filter(|line| !line.starts_with("total "))
+        .filter_map(parse_ls_line)
+        .collect();
+
+    let truncated = entries.len() > LIST_ENTRY_LIMIT;
+    sort_entries(&mut entries);
+    entries.truncate(LIST_ENTRY_LIMIT);
+
+    PathContents::Directory { entries, truncated }
+}
+
+/// Parses a line such as `lrwxrwxrwx 1 root root 7 1700000000 lib -> usr/lib`
+fn parse_ls_line(line: &str) -> Option<FileEntry> {
+    let mode = line.chars().next()?;
+
+    // Devices show "major, minor" in place of the size
+    let is_device = matches!(mode, 'c' | 'b');
+    let (fields, name) = split_fields(line, if is_device { 7 } else { 6 })?;
+
+    let (kind, name) = match mode {
+        'd' => (FileKind::Directory, name),
+        'l' => {
+            let (name, target) = name.split_once(" -> ").unwrap_or((name, ""));
+            (FileKind::Symlink(target.to_string()), name)
+        }
+        '-' => (FileKind::File, name),
+        _ => (FileKind::Other, name),
+    };
+
+    Some(FileEntry {
+        name: name.to_string(),
+        size: match kind {
+            FileKind::File => fields[4].parse().unwrap_or(0),
+            _ => 0,
+        },
+        kind,
+    })
+}
+
+/// Splits off `count` whitespace-separated fields, returning them and the rest of the line
+fn split_fields(line: &str, count: usize) -> Option<(Vec<&str>, &str)> {
+    let mut fields = Vec::with_capacity(count);
+    let mut rest = line;
+
+    for _ in 0..count {
+        rest = rest.trim_start();
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+
+    // Exactly one separator precedes the name, which may itself start with spaces
+    let name = rest.strip_prefix(' ').unwrap_or(rest);
+    (!name.is_empty()).then_some((fields, name))
+}
+
+/// Parses an archive returned for a single path
+/// The first entry is the path itself; for directories the direct children are collected
+/// `complete` is false when the archive was cut off, in which case a trailing partial entry
+/// is ignored and the listing is marked truncated
+pub fn parse_path_archive(data: &[u8], complete: bool) -> Result<PathContents, String> {
+    let mut archive = tar::Archive::new(data);
+    let mut entries = archive.entries().map_err(|e| e.to_string())?;
+
+    let mut root = match entries.next() {
+        Some(entry) => entry.map_err(|e| e.to_string())?,
+        None => return Err("Empty archive".to_string()),
+    };
+
+    let root_path = normalized_components(&root.path().map_err(|e| e.to_string())?);
+
+    match file_kind(&root) {
+        FileKind::File => {
+            let size = root.header().size().unwrap_or(0);
+            let mut buf = Vec::new();
+            let _ = (&mut root)
+                .take(PREVIEW_BYTE_LIMIT as u64)
+                .read_to_end(&mut buf);
+            Ok(PathContents::File(build_preview(&buf, size)))
+        }
+        FileKind::Symlink(target) => Ok(PathContents::Symlink(target)),
+        FileKind::Other => Err("Not a regular file or directory".to_string()),
+        FileKind::Directory => {
+            let mut children = Vec::new();
+            let mut truncated = !complete;
+
+            for entry in entries {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    // A cut-off archive ends with a partial entry
+                    Err(_) if !complete => break,
+                    Err(e) => {
+                        truncated = true;
+                        if children.is_empty() {
+                            return Err(e.to_string());
+                        }
+                        break;
+                    }
+                };
+
+                let Ok(path) = entry.path() else {
+                    continue;
+                };
+                let components = normalized_components(&path);
+
+                // Only direct children of the requested directory
+                if components.len() == root_path.len() + 1 && components.starts_with(&root_path) {
+                    children.push(FileEntry {
+                        name: components[root_path.len()].clone(),
+                        kind: file_kind(&entry),
+                        size: entry.header().size().unwrap_or(0),
+                    });
+                }
+            }
+
+            sort_entries(&mut children);
+            Ok(PathContents::Directory {
+                entries: children,
+                truncated,
+            })
+        }
+    }
+}
+
+/// Downloads a container path to a local destination and reports the outcome
+/// Destinations ending in `.tar` receive the archive as-is; anything else is treated as a
+/// directory the archive is extracted into
+///
+/// # Arguments
+/// * `host` - Docker host instance with identifier
+/// * `container_id` - Container ID (truncated IDs are accepted by the Docker API)
+/// * `path` - Absolute path inside the container
+/// * `destination` - Local file or directory path
+/// * `tx` - Event sender channel
+pub async fn download_container_path(
+    host: DockerHost,
+    container_id: String,
+    path: String,
+    destination: PathBuf,
+    tx: EventSender,
+) {
+    let key = ContainerKey::new(host.host_id.clone(), container_id.clone());
+
+    let result = if is_tar_destination(&destination) {
+        save_archive(&host, &container_id, &path, &destination)
+            .await
+            .map(|_| format!("Saved {} to {}", path, destination.display()))
+    } else {
+        extract_archive(&host, &container_id, &path, &destination)
+            .await
+            .map(|_| format!("Extracted {} into {}", path, destination.display()))
+    };
+
+    let _ = tx
+        .send(AppEvent::DownloadFinished(
+            key,
+            result.map_err(|e| format!("Download failed: {}", e)),
+        ))
+        .await;
+}
+
+/// Streams a path's archive into a local tar file
+async fn save_archive(
+    host: &DockerHost,
+    container_id: &str,
+    path: &str,
+    destination: &Path,
+) -> Result<(), String> {
+    let file = tokio::fs::File::create(destination)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    write_archive(host, container_id, path, file).await
+}
+
+/// Streams a path's archive into an open file
+async fn write_archive(
+    host: &DockerHost,
+    container_id: &str,
+    path: &str,
+    mut file: tokio::fs::File,
+) -> Result<(), String> {
+    let options = Some(DownloadFromContainerOptions {
+        path: path.to_string(),
+    });
+    let mut stream = host.docker.download_from_container(container_id, options);
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| e.to_string())?;
+        file.write_all(&chunk).await.map_err(|e| e.to_string())?;
+    }
+
+    file.flush().await.map_err(|e| e.to_string())
+}
+
+/// Downloads a path's archive to a temporary file and extracts it into a local directory
+async fn extract_archive(
+    host: &DockerHost,
+    container_id: &str,
+    path: &str,
+    destination: &Path,
+) -> Result<(), String> {
+    tokio::fs::create_dir_all(destination)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let (temp_path, temp_file) = create_temp_archive(container_id).await?;
+
+    let result = match write_archive(host, container_id, path, temp_file).await {
+        Ok(()) => {
+            let archive_path = temp_path.clone();
+            let destination = destination.to_path_buf();
+            tokio::task::spawn_blocking(move || -> Result<(), String> {
+                let file = std::fs::File::open(&archive_path).map_err(|e| e.to_string())?;
+                // unpack() refuses entries that would escape the destination
+                tar::Archive::new(file)
+                    .unpack(&destination)
+                    .map_err(|e| e.to_string())
+            })
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r)
+        }
+        Err(e) => Err(e),
+    };
+
+    let _ = tokio::fs::remove_file(&temp_path).await;
+    result
+}
+
+/// Creates a new temporary file for a downloaded archive
+/// `create_new` never opens an existing path, so a file or symlink planted at the name
+/// is not followed, and a counter keeps concurrent downloads apart
+async fn create_temp_archive(container_id: &str) -> Result<(PathBuf, tokio::fs::File), String> {
+    static NEXT_DOWNLOAD: AtomicU64 = AtomicU64::new(0);
+
+    for _ in 0..100 {
+        let temp_path = std::env::temp_dir().join(format!(
+            "dtui-download-{}-{}-{}.tar",
+            std::process::id(),
+            container_id,
+            NEXT_DOWNLOAD.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        match tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)
+            .await
+        {
+            Ok(file) => return Ok((temp_path, file)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    Err("Could not create a temporary file".to_string())
+}
+
+/// Whether a download destination should receive the raw tar archive
+pub fn is_tar_destination(destination: &Path) -> bool {
+    destination
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("tar"))
+}
+
+/// Expands a leading `~` to the home directory in a typed destination path
+pub fn expand_destination(input: &str) -> PathBuf {
+    let input = input.trim();
+    match input.strip_prefix("~") {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => match dirs::home_dir() {
+            Some(home) => home.join(rest.trim_start_matches('/')),
+            None => PathBuf::from(input),
+        },
+        _ => PathBuf::from(input),
+    }
+}
+
+/// Joins a directory and an entry name into an absolute container path
+pub fn join_container_path(dir: &str, name: &str) -> String {
+    if dir.ends_with('/') {
+        format!("{}{}", dir, name)
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+/// Returns the parent directory of an absolute container path
+pub fn parent_container_path(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(idx) => trimmed[..idx].to_string(),
+    }
+}
+
+/// Resolves a symlink target relative to the directory containing the link
+pub fn resolve_link_target(dir: &str, target: &str) -> String {
+    let joined = if target.starts_with('/') {
+        target.to_string()
+    } else {
+        join_container_path(dir, target)
+    };
+
+    // Collapse "." and ".." so the archive API gets a clean path
+    let mut components: Vec<&str> = Vec::new();
+    for part in joined.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            other => components.push(other),
+        }
+    }
+
+    format!("/{}", components.join("/"))
+}
+
+/// Sorts directories first, then by name
+fn sort_entries(entries: &mut [FileEntry]) {
+    entries.sort_by(|a, b| {
+        let a_dir = a.kind == FileKind::Directory;
+        let b_dir = b.kind == FileKind::Directory;
+        b_dir.cmp(&a_dir).then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Maps a tar entry type to a file kind
+fn file_kind<R: Read>(entry: &tar::Entry<R>) -> FileKind {
+    let entry_type = entry.header().entry_type();
+
+    if entry_type.is_dir() {
+        FileKind::Directory
+    } else if entry_type.is_symlink() {
+        let target = entry
+            .link_name()
+            .ok()
+            .flatten()
+            .map(|t| t.to_string_lossy().to_string())
+            .unwrap_or_default();
+        FileKind::Symlink(target)
+    } else if entry_type.is_file() || entry_type.is_hard_link() || entry_type.is_contiguous() {
+        FileKind::File
+    } else {
+        FileKind::Other
+    }
+}
+
+/// Splits an archive path into its normal components, dropping "." and "/"
+fn normalized_components(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Builds a preview, treating data with NUL bytes or invalid UTF-8 as binary
+fn build_preview(data: &[u8], size: u64) -> FilePreview {
+    // An incomplete character at the cut-off point doesn't make the file binary
+    let invalid_utf8 = std::str::from_utf8(data)
+        .err()
+        .is_some_and(|e| e.error_len().is_some());
+    let binary = data.contains(&0) || invalid_utf8;
+
+    FilePreview {
+        text: if binary {
+            String::new()
+        } else {
+            String::from_utf8_lossy(data).to_string()
+        },
+        size,
+        truncated: size > data.len() as u64,
+        binary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds an in-memory tar archive like the one returned by the archive API
+    fn build_archive(entries: &[(&str, tar::EntryType, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+
+        for (path, entry_type, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(*entry_type);
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            if entry_type.is_symlink() {
+                header
+                    .set_link_name(std::str::from_utf8(data).unwrap())
+                    .unwrap();
+                header.set_size(0);
+                builder.append_data(&mut header, path, &[][..]).unwrap();
+            } else {
+                builder.append_data(&mut header, path, *data).unwrap();
+            }
+        }
+
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_parse_directory_lists_direct_children() {
+        let data = build_archive(&[
+            ("etc/", tar::EntryType::Directory, b""),
+            (
+                "etc/hosts",
+                tar::EntryType::Regular,
+                b"127.0.0.1 localhost\n",
+            ),
+            ("etc/ssl/", tar::EntryType::Directory, b""),
+            ("etc/ssl/cert.pem", tar::EntryType::Regular, b"cert"),
+            (
+                "etc/localtime",
+                tar::EntryType::Symlink,
+                b"/usr/share/zoneinfo/UTC",
+            ),
+        ]);
+
+        let contents = parse_path_archive(&data, true).unwrap();
+
+        assert_eq!(
+            contents,
+            PathContents::Directory {
+                entries: vec![
+                    FileEntry {
+                        name: "ssl".to_string(),
+                        kind: FileKind::Directory,
+                        size: 0,
+                    },
+                    FileEntry {
+                        name: "hosts".to_string(),
+                        kind: FileKind::File,
+                        size: 20,
+                    },
+                    FileEntry {
+                        name: "localtime".to_string(),
+                        kind: FileKind::Symlink("/usr/share/zoneinfo/UTC".to_string()),
+                        size: 0,
+                    },
+                ],
+                truncated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_truncated_directory() {
+        let data = build_archive(&[
+            ("var/", tar::EntryType::Directory, b""),
+            ("var/a.log", tar::EntryType::Regular, b"first"),
+            ("var/b.log", tar::EntryType::Regular, &[b'x'; 2048]),
+        ]);
+
+        // Cut the archive off in the middle of the last file
+        let cut = &data[..data.len() - 2048];
+        let PathContents::Directory { entries, truncated } =
+            parse_path_archive(cut, false).unwrap()
+        else {
+            panic!("expected a directory");
+        };
+
+        assert!(truncated);
+        assert!(entries.iter().any(|e| e.name == "a.log"));
+    }
+
+    #[test]
+    fn test_parse_ls_output() {
+        let output = "total 12\n\
+            drwxr-xr-x    2 root root 4096 1700000000 usr\n\
+            -rw-r--r--    1 root root   20 1700000000 my notes.txt\n\
+            lrwxrwxrwx    1 root root    7 1700000000 lib -> usr/lib\n\
+            crw-rw-rw-    1 root root 1,   3 1700000000 null\n";
+
+        assert_eq!(
+            parse_ls_output(output),
+            PathContents::Directory {
+                entries: vec![
+                    FileEntry {
+                        name: "usr".to_string(),
+                        kind: FileKind::Directory,
+                        size: 0,
+                    },
+                    FileEntry {
+                        name: "lib".to_string(),
+                        kind: FileKind::Symlink("usr/lib".to_string()),
+                        size: 0,
+                    },
+                    FileEntry {
+                        name: "my notes.txt".to_string(),
+                        kind: FileKind::File,
+                        size: 20,
+                    },
+                    FileEntry {
+                        name: "null".to_string(),
+                        kind: FileKind::Other,
+                        size: 0,
+                    },
+                ],
+                truncated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_ls_output_truncates_large_directories() {
+        let output: String = (0..LIST_ENTRY_LIMIT + 5)
+            .map(|i| format!("-rw-r--r-- 1 root root 1 1700000000 file{}\n", i))
+            .collect();
+
+        let PathContents::Directory { entries, truncated } = parse_ls_output(&output) else {
+            panic!("expected a directory");
+        };
+        assert!(truncated);
+        assert_eq!(entries.len(), LIST_ENTRY_LIMIT);
+    }
+
+    #[test]
+    fn test_parse_file_preview() {
+        let data = build_archive(&[("hosts", tar::EntryType::Regular, b"127.0.0.1 localhost\n")]);
+
+        assert_eq!(
+            parse_path_archive(&data, true).unwrap(),
+            PathContents::File(FilePreview {
+                text: "127.0.0.1 localhost\n".to_string(),
+                size: 20,
+                truncated: false,
+                binary: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_binary_file_preview() {
+        let data = build_archive(&[("app", tar::EntryType::Regular, b"\x7fELF\x00\x01")]);
+
+        let PathContents::File(preview) = parse_path_archive(&data, true).unwrap() else {
+            panic!("expected a file");
+        };
+        assert!(preview.binary);
+        assert!(preview.text.is_empty());
+    }
+
+    #[test]
+    fn test_parse_symlink() {
+        let data = build_archive(&[("localtime", tar::EntryType::Symlink, b"../usr/zoneinfo")]);
+
+        assert_eq!(
+            parse_path_archive(&data, true).unwrap(),
+            PathContents::Symlink("../usr/zoneinfo".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_archive() {
+        assert!(parse_path_archive(&[], true).is_err());
+    }
+
+    #[test]
+    fn test_container_path_helpers() {
+        assert_eq!(join_container_path("/", "etc"), "/etc");
+        assert_eq!(join_container_path("/etc", "hosts"), "/etc/hosts");
+
+        assert_eq!(parent_container_path("/etc/ssl"), "/etc");
+        assert_eq!(parent_container_path("/etc"), "/");
+        assert_eq!(parent_container_path("/"), "/");
+
+        assert_eq!(resolve_link_target("/etc", "/usr/share"), "/usr/share");
+        assert_eq!(resolve_link_target("/etc", "../usr/./lib"), "/usr/lib");
+        assert_eq!(resolve_link_target("/", "../.."), "/");
+    }
+
+    #[test]
+    fn test_download_destination_helpers() {
+        assert!(is_tar_destination(Path::new("./etc.tar")));
+        assert!(is_tar_destination(Path::new("/tmp/backup.TAR")));
+        assert!(!is_tar_destination(Path::new("./etc")));
+
+        assert_eq!(expand_destination(" ./out "), PathBuf::from("./out"));
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(expand_destination("~/out"), home.join("out"));
+        }
+    }
+}
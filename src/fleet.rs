@@ -0,0 +1,191 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::types::{Container, ContainerKey, HostId};
+
+/// A service's containers on one host of the fleet
+#[derive(Clone, Debug, PartialEq)]
+pub struct FleetHost {
+    pub host_id: HostId,
+    /// Monitored containers of the service on this host (empty when the service is missing)
+    pub containers: Vec<ContainerKey>,
+    /// How many of the containers are paused
+    pub paused: usize,
+    /// Summed CPU usage in percent of one core
+    pub cpu: f64,
+    pub memory_used_bytes: u64,
+    /// Whether any of the containers reports an unhealthy health check
+    pub unhealthy: bool,
+}
+
+impl FleetHost {
+    pub fn is_missing(&self) -> bool {
+        self.containers.is_empty()
+    }
+
+    /// Number of containers that are not paused
+    pub fn running(&self) -> usize {
+        self.containers.len() - self.paused
+    }
+}
+
+/// The same service across all connected hosts
+#[derive(Clone, Debug, PartialEq)]
+pub struct FleetService {
+    pub name: String,
+    /// One entry per connected host, in host order
+    pub hosts: Vec<FleetHost>,
+}
+
+impl FleetService {
+    /// Whether the service is missing, paused or unhealthy on any host
+    pub fn is_degraded(&self) -> bool {
+        self.hosts
+            .iter()
+            .any(|host| host.is_missing() || host.paused > 0 || host.unhealthy)
+    }
+}
+
+/// Name identifying a container's service across hosts - the value of `label`
+/// when configured and set on the container, otherwise the container name
+fn service_name<'a>(container: &'a Container, label: Option<&str>) -> &'a str {
+    label
+        .and_then(|label| container.labels.get(label))
+        .map(String::as_str)
+        .unwrap_or(&container.name)
+}
+
+/// Groups the containers of all hosts by service, sorted by service name
+///
+/// # Arguments
+/// * `containers` - All monitored containers
+/// * `hosts` - Connected hosts, in the order their columns are shown
+/// * `label` - Label identifying the service, or None to match by container name
+pub fn build_fleet(
+    containers: &HashMap<ContainerKey, Container>,
+    hosts: &[&HostId],
+    label: Option<&str>,
+) -> Vec<FleetService> {
+    let mut services: BTreeMap<&str, Vec<(&ContainerKey, &Container)>> = BTreeMap::new();
+    for (key, container) in containers {
+        services
+            .entry(service_name(container, label))
+            .or_default()
+            .push((key, container));
+    }
+
+    services
+        .into_iter()
+        .map(|(name, members)| FleetService {
+            name: name.to_string(),
+            hosts: hosts
+                .iter()
+                .map(|&host_id| {
+                    let on_host: Vec<_> = members
+                        .iter()
+                        .filter(|(key, _)| &key.host_id == host_id)
+                        .collect();
+
+                    let mut containers: Vec<ContainerKey> =
+                        on_host.iter().map(|(key, _)| (*key).clone()).collect();
+                    containers.sort_by(|a, b| a.container_id.cmp(&b.container_id));
+
+                    FleetHost {
+                        host_id: host_id.clone(),
+                        containers,
+                        paused: on_host.iter().filter(|(_, c)| c.status == "paused").count(),
+                        cpu: on_host.iter().map(|(_, c)| c.stats.cpu).sum(),
+                        memory_used_bytes: on_host
+                            .iter()
+                            .map(|(_, c)| c.stats.memory_used_bytes.unwrap_or(0))
+                            .sum(),
+                        unhealthy: on_host.iter().any(|(_, c)| c.is_unhealthy()),
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::Health;
+    use crate::types::ContainerStats;
+
+    fn container(host_id: &str, id: &str, name: &str, health: Health) -> Container {
+        Container {
+            name: name.to_string(),
+            stats: ContainerStats {
+                cpu: 10.0,
+                ..Default::default()
+            },
+            labels: HashMap::from([("app".to_string(), format!("app-{}", name))]),
+            health,
+            ..Container::for_test(host_id, id)
+        }
+    }
+
+    fn containers(list: Vec<Container>) -> HashMap<ContainerKey, Container> {
+        list.into_iter()
+            .map(|c| (ContainerKey::new(c.host_id.clone(), c.id.clone()), c))
+            .collect()
+    }
+
+    #[test]
+    fn test_fleet_by_name_flags_missing_and_unhealthy() {
+        let containers = containers(vec![
+            container("a", "1", "web", Health::None),
+            container("b", "2", "web", Health::Unhealthy),
+            container("a", "3", "db", Health::None),
+            container("a", "4", "db", Health::None),
+        ]);
+        let (a, b) = ("a".to_string(), "b".to_string());
+
+        let fleet = build_fleet(&containers, &[&a, &b], None);
+
+        assert_eq!(fleet.len(), 2);
+        assert_eq!(fleet[0].name, "db");
+        assert_eq!(fleet[0].hosts[0].containers.len(), 2);
+        assert_eq!(fleet[0].hosts[0].cpu, 20.0);
+        assert!(fleet[0].hosts[1].is_missing());
+        assert!(fleet[0].is_degraded());
+
+        assert_eq!(fleet[1].name, "web");
+        assert!(!fleet[1].hosts[0].unhealthy);
+        assert!(fleet[1].hosts[1].unhealthy);
+        assert!(fleet[1].is_degraded());
+    }
+
+    #[test]
+    fn test_fleet_by_label() {
+        let containers = containers(vec![
+            container("a", "1", "web", Health::None),
+            container("b", "2", "web", Health::None),
+        ]);
+        let (a, b) = ("a".to_string(), "b".to_string());
+
+        let fleet = build_fleet(&containers, &[&a, &b], Some("app"));
+
+        assert_eq!(fleet.len(), 1);
+        assert_eq!(fleet[0].name, "app-web");
+        assert!(!fleet[0].is_degraded());
+    }
+
+    #[test]
+    fn test_fleet_counts_paused_containers() {
+        let containers = containers(vec![
+            container("a", "1", "web", Health::None),
+            Container {
+                status: "paused".to_string(),
+                ..container("a", "2", "web", Health::None)
+            },
+        ]);
+        let a = "a".to_string();
+
+        let fleet = build_fleet(&containers, &[&a], None);
+
+        assert_eq!(fleet[0].hosts[0].running(), 1);
+        assert_eq!(fleet[0].hosts[0].paused, 1);
+        assert!(fleet[0].is_degraded());
+    }
+}
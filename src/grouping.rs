@@ -0,0 +1,287 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{Container, ContainerKey, GroupMode, HostId};
+
+/// Label set by Docker Compose on every container of a project
+pub const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+/// Label set by Docker Compose with the name of the container's service
+pub const COMPOSE_SERVICE_LABEL: &str = "com.docker.compose.service";
+
+/// A group of containers in the container list
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum GroupKey {
+    /// All containers of a host
+    Host(HostId),
+    /// A Compose project on a host (None groups the containers that aren't part of a project)
+    Project {
+        host_id: HostId,
+        project: Option<String>,
+    },
+}
+
+impl GroupKey {
+    pub fn host_id(&self) -> &HostId {
+        match self {
+            GroupKey::Host(host_id) | GroupKey::Project { host_id, .. } => host_id,
+        }
+    }
+
+    /// Orders groups by host, then project with containers outside a project last
+    fn sort_key(&self) -> (&HostId, bool, Option<&String>) {
+        match self {
+            GroupKey::Host(host_id) => (host_id, false, None),
+            GroupKey::Project { host_id, project } => {
+                (host_id, project.is_none(), project.as_ref())
+            }
+        }
+    }
+}
+
+/// Header row of a group with usage aggregated over its containers
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupSummary {
+    pub key: GroupKey,
+    pub containers: usize,
+    pub services: usize,
+    /// Summed CPU usage in percent of one core
+    pub cpu: f64,
+    pub memory_used_bytes: u64,
+    pub network_tx_bytes_per_sec: f64,
+    pub network_rx_bytes_per_sec: f64,
+    pub collapsed: bool,
+}
+
+/// A row of the container list - a group header or a container
+#[derive(Clone, Debug, PartialEq)]
+pub enum ListRow {
+    Group(GroupSummary),
+    Container(ContainerKey),
+}
+
+/// Arranges the listed containers into rows for the given grouping mode
+///
+/// Without grouping the containers keep their order. Grouped by host, each host's
+/// containers keep their order below its section header. Grouped by Compose project,
+/// groups are sorted by host and project (containers outside a project last),
+/// and containers within a group by service and name. Collapsed groups only
+/// contribute their header row.
+pub fn build_list_rows(
+    keys: &[&ContainerKey],
+    containers: &HashMap<ContainerKey, Container>,
+    mode: GroupMode,
+    collapsed: &HashSet<GroupKey>,
+) -> Vec<ListRow> {
+    if mode == GroupMode::None {
+        return keys
+            .iter()
+            .map(|&key| ListRow::Container(key.clone()))
+            .collect();
+    }
+
+    let mut groups: HashMap<GroupKey, Vec<&Container>> = HashMap::new();
+    for key in keys {
+        if let Some(container) = containers.get(key) {
+            let group_key = match mode {
+                GroupMode::Host => GroupKey::Host(key.host_id.clone()),
+                _ => GroupKey::Project {
+                    host_id: key.host_id.clone(),
+                    project: container.compose_project().map(str::to_string),
+                },
+            };
+            groups.entry(group_key).or_default().push(container);
+        }
+    }
+
+    let mut groups: Vec<(GroupKey, Vec<&Container>)> = groups.into_iter().collect();
+    groups.sort_by(|(a, _), (b, _)| a.sort_key().cmp(&b.sort_key()));
+
+    let mut rows = Vec::new();
+    for (key, mut members) in groups {
+        if mode == GroupMode::Compose {
+            members.sort_by(|a, b| {
+                (a.compose_service(), &a.name).cmp(&(b.compose_service(), &b.name))
+            });
+        }
+
+        let services: HashSet<_> = members.iter().filter_map(|c| c.compose_service()).collect();
+        let is_collapsed = collapsed.contains(&key);
+
+        rows.push(ListRow::Group(GroupSummary {
+            containers: members.len(),
+            services: services.len(),
+            cpu: members.iter().map(|c| c.stats.cpu).sum(),
+            memory_used_bytes: members
+                .iter()
+                .map(|c| c.stats.memory_used_bytes.unwrap_or(0))
+                .sum(),
+            network_tx_bytes_per_sec: members
+                .iter()
+                .map(|c| c.stats.network_tx_bytes_per_sec)
+                .sum(),
+            network_rx_bytes_per_sec: members
+                .iter()
+                .map(|c| c.stats.network_rx_bytes_per_sec)
+                .sum(),
+            collapsed: is_collapsed,
+            key,
+        }));
+
+        if !is_collapsed {
+            rows.extend(
+                members.iter().map(|c| {
+                    ListRow::Container(ContainerKey::new(c.host_id.clone(), c.id.clone()))
+                }),
+            );
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ContainerStats;
+
+    fn container(id: &str, name: &str, project: Option<&str>, service: Option<&str>) -> Container {
+        let mut labels = HashMap::new();
+        if let Some(project) = project {
+            labels.insert(COMPOSE_PROJECT_LABEL.to_string(), project.to_string());
+        }
+        if let Some(service) = service {
+            labels.insert(COMPOSE_SERVICE_LABEL.to_string(), service.to_string());
+        }
+
+        Container {
+            name: name.to_string(),
+            stats: ContainerStats {
+                cpu: 10.0,
+                memory_used_bytes: Some(100),
+                ..Default::default()
+            },
+            labels,
+            ..Container::for_test("local", id)
+        }
+    }
+
+    fn fixture() -> (Vec<ContainerKey>, HashMap<ContainerKey, Container>) {
+        let containers: HashMap<_, _> = [
+            container("1", "standalone", None, None),
+            container("2", "shop-web-2", Some("shop"), Some("web")),
+            container("3", "shop-db-1", Some("shop"), Some("db")),
+            container("4", "shop-web-1", Some("shop"), Some("web")),
+            container("5", "blog-app-1", Some("blog"), Some("app")),
+        ]
+        .into_iter()
+        .map(|c| (ContainerKey::new(c.host_id.clone(), c.id.clone()), c))
+        .collect();
+
+        let mut keys: Vec<_> = containers.keys().cloned().collect();
+        keys.sort_by(|a, b| a.container_id.cmp(&b.container_id));
+        (keys, containers)
+    }
+
+    fn describe(rows: &[ListRow], containers: &HashMap<ContainerKey, Container>) -> Vec<String> {
+        rows.iter()
+            .map(|row| match row {
+                ListRow::Group(group) => {
+                    let name = match &group.key {
+                        GroupKey::Host(host_id) => host_id.as_str(),
+                        GroupKey::Project { project, .. } => project.as_deref().unwrap_or("-"),
+                    };
+                    format!("{} ({})", name, group.containers)
+                }
+                ListRow::Container(key) => containers[key].name.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rows_without_grouping_keep_order() {
+        let (keys, containers) = fixture();
+        let key_refs: Vec<_> = keys.iter().collect();
+
+        let rows = build_list_rows(&key_refs, &containers, GroupMode::None, &HashSet::new());
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0], ListRow::Container(keys[0].clone()));
+    }
+
+    #[test]
+    fn test_rows_grouped_by_compose_project() {
+        let (keys, containers) = fixture();
+        let key_refs: Vec<_> = keys.iter().collect();
+
+        let rows = build_list_rows(&key_refs, &containers, GroupMode::Compose, &HashSet::new());
+
+        assert_eq!(
+            describe(&rows, &containers),
+            vec![
+                "blog (1)",
+                "blog-app-1",
+                "shop (3)",
+                "shop-db-1",
+                "shop-web-1",
+                "shop-web-2",
+                "- (1)",
+                "standalone",
+            ]
+        );
+
+        let ListRow::Group(shop) = &rows[2] else {
+            panic!("expected a group row");
+        };
+        assert_eq!(shop.services, 2);
+        assert_eq!(shop.cpu, 30.0);
+        assert_eq!(shop.memory_used_bytes, 300);
+    }
+
+    #[test]
+    fn test_rows_in_host_sections() {
+        let (mut keys, mut containers) = fixture();
+        let mut remote = container("6", "cache", None, None);
+        remote.host_id = "remote".to_string();
+        let remote_key = ContainerKey::new(remote.host_id.clone(), remote.id.clone());
+        containers.insert(remote_key.clone(), remote);
+        keys.insert(0, remote_key);
+        let key_refs: Vec<_> = keys.iter().collect();
+
+        let collapsed = HashSet::from([GroupKey::Host("remote".to_string())]);
+        let rows = build_list_rows(&key_refs, &containers, GroupMode::Host, &collapsed);
+
+        assert_eq!(
+            describe(&rows, &containers),
+            vec![
+                "local (5)",
+                "standalone",
+                "shop-web-2",
+                "shop-db-1",
+                "shop-web-1",
+                "blog-app-1",
+                "remote (1)",
+            ]
+        );
+        let ListRow::Group(local) = &rows[0] else {
+            panic!("expected a group row");
+        };
+        assert_eq!(local.cpu, 50.0);
+        assert!(!local.collapsed);
+    }
+
+    #[test]
+    fn test_collapsed_group_only_shows_header() {
+        let (keys, containers) = fixture();
+        let key_refs: Vec<_> = keys.iter().collect();
+        let collapsed = HashSet::from([GroupKey::Project {
+            host_id: "local".to_string(),
+            project: Some("shop".to_string()),
+        }]);
+
+        let rows = build_list_rows(&key_refs, &containers, GroupMode::Compose, &collapsed);
+
+        assert_eq!(
+            describe(&rows, &containers),
+            vec!["blog (1)", "blog-app-1", "shop (3)", "- (1)", "standalone"]
+        );
+    }
+}
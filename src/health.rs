@@ -0,0 +1,126 @@
+use bollard::query_parameters::InspectContainerOptions;
+
+use crate::docker::DockerHost;
+use crate::types::{AppEvent, ContainerKey, EventSender};
+
+/// Health check state of a container
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Health {
+    /// The container has no health check
+    #[default]
+    None,
+    Starting,
+    Healthy,
+    Unhealthy,
+}
+
+impl Health {
+    /// Parses a health status as reported by inspect and `health_status` events
+    pub fn from_status(status: &str) -> Self {
+        match status.trim() {
+            "starting" => Health::Starting,
+            "healthy" => Health::Healthy,
+            "unhealthy" => Health::Unhealthy,
+            _ => Health::None,
+        }
+    }
+
+    /// Parses the health suffix of a container list status, e.g. "Up 2 hours (healthy)"
+    /// Only the initial container list is read this way, later changes come from health events
+    pub fn from_list_status(status: &str) -> Self {
+        if status.contains("(unhealthy)") {
+            Health::Unhealthy
+        } else if status.contains("(healthy)") {
+            Health::Healthy
+        } else if status.contains("(health: starting)") {
+            Health::Starting
+        } else {
+            Health::None
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Health::None => "none",
+            Health::Starting => "starting",
+            Health::Healthy => "healthy",
+            Health::Unhealthy => "unhealthy",
+        }
+    }
+}
+
+/// Result of a single health check run
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthCheck {
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub exit_code: Option<i64>,
+    pub output: String,
+}
+
+/// Fetches the most recent health check results of a container, oldest first
+///
+/// # Arguments
+/// * `host` - Docker host instance with identifier
+/// * `container_id` - Container ID (truncated IDs are accepted by the Docker API)
+/// * `tx` - Event sender channel
+pub async fn fetch_health_log(host: DockerHost, container_id: String, tx: EventSender) {
+    let key = ContainerKey::new(host.host_id.clone(), container_id.clone());
+
+    let result = host
+        .docker
+        .inspect_container(&container_id, None::<InspectContainerOptions>)
+        .await
+        .map(|inspect| {
+            inspect
+                .state
+                .and_then(|state| state.health)
+                .and_then(|health| health.log)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|check| HealthCheck {
+                    start: check.start.map(|date| date.to_string()),
+                    end: check.end.map(|date| date.to_string()),
+                    exit_code: check.exit_code,
+                    output: check.output.unwrap_or_default().trim().to_string(),
+                })
+                .collect()
+        })
+        .map_err(|e| e.to_string());
+
+    let _ = tx.send(AppEvent::HealthLog(key, result)).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_from_status() {
+        assert_eq!(Health::from_status("healthy"), Health::Healthy);
+        assert_eq!(Health::from_status(" unhealthy"), Health::Unhealthy);
+        assert_eq!(Health::from_status("starting"), Health::Starting);
+        assert_eq!(Health::from_status(""), Health::None);
+    }
+
+    #[test]
+    fn test_health_from_list_status() {
+        assert_eq!(
+            Health::from_list_status("Up 2 hours (healthy)"),
+            Health::Healthy
+        );
+        assert_eq!(
+            Health::from_list_status("Up 5 minutes (unhealthy)"),
+            Health::Unhealthy
+        );
+        assert_eq!(
+            Health::from_list_status("Up 3 seconds (health: starting)"),
+            Health::Starting
+        );
+        assert_eq!(Health::from_list_status("Up 2 hours"), Health::None);
+        assert_eq!(
+            Health::from_list_status("Up 3 minutes (Paused)"),
+            Health::None
+        );
+    }
+}
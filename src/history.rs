@@ -0,0 +1,221 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::types::ContainerStats;
+
+/// Time windows the metrics charts can show, cycled with 'w'
+pub const CHART_WINDOWS: [Duration; 4] = [
+    Duration::from_secs(60),
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(15 * 60),
+    Duration::from_secs(30 * 60),
+];
+
+/// Number of samples kept per container when not configured
+/// Stats arrive about once per second, so this fills the longest chart window
+pub const DEFAULT_HISTORY_LENGTH: usize = CHART_WINDOWS[CHART_WINDOWS.len() - 1].as_secs() as usize;
+
+/// Number of chart windows a history of `capacity` samples can fill, always at least one
+pub fn chart_window_count(capacity: usize) -> usize {
+    CHART_WINDOWS
+        .iter()
+        .filter(|window| window.as_secs() as usize <= capacity)
+        .count()
+        .max(1)
+}
+
+/// Characters used to draw sparklines, from lowest to highest
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// A single stats sample in a container's history
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistorySample {
+    pub at: Instant,
+    pub cpu: f64,
+    pub memory: f64,
+    pub memory_used_bytes: f64,
+    pub network_tx_bytes_per_sec: f64,
+    pub network_rx_bytes_per_sec: f64,
+    pub block_read_bytes_per_sec: f64,
+    pub block_write_bytes_per_sec: f64,
+}
+
+impl HistorySample {
+    pub fn from_stats(stats: &ContainerStats, at: Instant) -> Self {
+        Self {
+            at,
+            cpu: stats.cpu,
+            memory: stats.memory,
+            memory_used_bytes: stats.memory_used_bytes.unwrap_or(0) as f64,
+            network_tx_bytes_per_sec: stats.network_tx_bytes_per_sec,
+            network_rx_bytes_per_sec: stats.network_rx_bytes_per_sec,
+            block_read_bytes_per_sec: stats.block_read_bytes_per_sec,
+            block_write_bytes_per_sec: stats.block_write_bytes_per_sec,
+        }
+    }
+}
+
+/// Rolling window of the most recent stats samples of a container
+#[derive(Clone, Debug)]
+pub struct StatsHistory {
+    capacity: usize,
+    samples: VecDeque<HistorySample>,
+}
+
+impl StatsHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Appends a sample, dropping the oldest once the history is full
+    pub fn push(&mut self, sample: HistorySample) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// The most recent `count` values of a metric, oldest first
+    pub fn recent(&self, count: usize, metric: impl Fn(&HistorySample) -> f64) -> Vec<f64> {
+        let skip = self.samples.len().saturating_sub(count);
+        self.samples.iter().skip(skip).map(metric).collect()
+    }
+
+    /// Chart points of a metric within `window` before `now`, oldest first
+    /// X values are seconds relative to `now`, so they run from -window to 0
+    pub fn points(
+        &self,
+        window: Duration,
+        now: Instant,
+        metric: impl Fn(&HistorySample) -> f64,
+    ) -> Vec<(f64, f64)> {
+        self.samples
+            .iter()
+            .filter(|sample| now.saturating_duration_since(sample.at) <= window)
+            .map(|sample| {
+                let age = now.saturating_duration_since(sample.at).as_secs_f64();
+                (-age, metric(sample))
+            })
+            .collect()
+    }
+}
+
+/// Summary statistics of a metric over a time window
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+    /// 95th percentile (nearest rank)
+    pub p95: f64,
+}
+
+/// Summarizes values, or returns None when there are none
+pub fn summarize(values: &[f64]) -> Option<Summary> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let rank = (sorted.len() as f64 * 0.95).ceil() as usize;
+
+    Some(Summary {
+        min: sorted[0],
+        avg: sorted.iter().sum::<f64>() / sorted.len() as f64,
+        max: sorted[sorted.len() - 1],
+        p95: sorted[rank.saturating_sub(1)],
+    })
+}
+
+/// Renders values as a sparkline of block characters scaled between 0 and `max`
+/// Values above `max` are drawn as full blocks
+pub fn sparkline(values: &[f64], max: f64) -> String {
+    values
+        .iter()
+        .map(|&value| {
+            let fraction = if max > 0.0 {
+                (value / max).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let idx = (fraction * (SPARK_CHARS.len() - 1) as f64).round() as usize;
+            SPARK_CHARS[idx]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chart_window_count() {
+        assert_eq!(
+            chart_window_count(DEFAULT_HISTORY_LENGTH),
+            CHART_WINDOWS.len()
+        );
+        assert_eq!(chart_window_count(300), 2);
+        assert_eq!(chart_window_count(10), 1);
+    }
+
+    fn sample(cpu: f64) -> HistorySample {
+        HistorySample::from_stats(
+            &ContainerStats {
+                cpu,
+                ..Default::default()
+            },
+            Instant::now(),
+        )
+    }
+
+    #[test]
+    fn test_history_drops_oldest_samples() {
+        let mut history = StatsHistory::new(3);
+        for cpu in [1.0, 2.0, 3.0, 4.0] {
+            history.push(sample(cpu));
+        }
+
+        assert_eq!(history.recent(10, |s| s.cpu), vec![2.0, 3.0, 4.0]);
+        assert_eq!(history.recent(2, |s| s.cpu), vec![3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_sparkline_scaling() {
+        assert_eq!(sparkline(&[0.0, 50.0, 100.0, 150.0], 100.0), "▁▅██");
+        assert_eq!(sparkline(&[10.0], 0.0), "▁");
+        assert_eq!(sparkline(&[], 100.0), "");
+    }
+
+    #[test]
+    fn test_points_within_window() {
+        let now = Instant::now();
+        let mut history = StatsHistory::new(10);
+        for (age, cpu) in [(90, 1.0), (30, 2.0), (0, 3.0)] {
+            let mut sample = sample(cpu);
+            sample.at = now - Duration::from_secs(age);
+            history.push(sample);
+        }
+
+        let points = history.points(Duration::from_secs(60), now, |s| s.cpu);
+        assert_eq!(points, vec![(-30.0, 2.0), (0.0, 3.0)]);
+    }
+
+    #[test]
+    fn test_summarize() {
+        let values: Vec<f64> = (1..=100).map(f64::from).collect();
+        let summary = summarize(&values).unwrap();
+
+        assert_eq!(summary.min, 1.0);
+        assert_eq!(summary.max, 100.0);
+        assert_eq!(summary.avg, 50.5);
+        assert_eq!(summary.p95, 95.0);
+
+        assert_eq!(summarize(&[4.0]).unwrap().p95, 4.0);
+        assert!(summarize(&[]).is_none());
+    }
+}
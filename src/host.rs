@@ -0,0 +1,263 @@
+use bollard::models::{SystemDataUsageResponse, SystemInfo};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::docker::DockerHost;
+use crate::types::{AppEvent, Container, ContainerKey, EventSender, HostId};
+
+/// How often host capacity and container counts are refreshed
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Disk usage is expensive to calculate, so it's only refreshed every this many polls
+const DISK_USAGE_POLLS: u32 = 6;
+
+/// Capacity and engine details of a Docker host from the `info` API
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HostInfo {
+    /// Number of CPUs available to the engine
+    pub cpus: u64,
+    /// Total memory of the host in bytes
+    pub memory_total_bytes: u64,
+    pub containers_running: u64,
+    pub containers_paused: u64,
+    pub containers_stopped: u64,
+    pub engine_version: String,
+    pub operating_system: String,
+    /// Round trip time of the `info` request
+    pub latency: Duration,
+    /// Disk used by images, containers, volumes and build cache (None until first fetched)
+    pub disk_usage: Option<DiskUsage>,
+}
+
+impl HostInfo {
+    pub fn from_system_info(info: &SystemInfo) -> Self {
+        let count = |value: Option<i64>| value.unwrap_or(0).max(0) as u64;
+
+        Self {
+            cpus: count(info.ncpu),
+            memory_total_bytes: count(info.mem_total),
+            containers_running: count(info.containers_running),
+            containers_paused: count(info.containers_paused),
+            containers_stopped: count(info.containers_stopped),
+            engine_version: info.server_version.clone().unwrap_or_default(),
+            operating_system: info.operating_system.clone().unwrap_or_default(),
+            latency: Duration::ZERO,
+            disk_usage: None,
+        }
+    }
+}
+
+/// Disk space used by the Docker engine, in bytes
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DiskUsage {
+    pub images: u64,
+    /// Writable layers of all containers
+    pub containers: u64,
+    pub volumes: u64,
+    pub build_cache: u64,
+}
+
+impl DiskUsage {
+    pub fn from_data_usage(usage: &SystemDataUsageResponse) -> Self {
+        // Sizes are -1 when the daemon couldn't calculate them
+        let size = |value: Option<i64>| value.unwrap_or(0).max(0) as u64;
+
+        Self {
+            images: size(usage.layers_size),
+            containers: usage
+                .containers
+                .iter()
+                .flatten()
+                .map(|c| size(c.size_rw))
+                .sum(),
+            volumes: usage
+                .volumes
+                .iter()
+                .flatten()
+                .map(|v| size(v.usage_data.as_ref().map(|u| u.size)))
+                .sum(),
+            build_cache: usage
+                .build_cache
+                .iter()
+                .flatten()
+                .map(|b| size(b.size))
+                .sum(),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.images + self.containers + self.volumes + self.build_cache
+    }
+}
+
+/// Resources used by the monitored containers of a host
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HostUsage {
+    /// CPU cores in use across all containers
+    pub cpu_cores: f64,
+    /// Memory used by all containers in bytes
+    pub memory_used_bytes: u64,
+}
+
+impl HostUsage {
+    /// Percentage of the host's CPUs in use, if its capacity is known
+    pub fn cpu_percent(&self, info: &HostInfo) -> Option<f64> {
+        (info.cpus > 0).then(|| self.cpu_cores / info.cpus as f64 * 100.0)
+    }
+
+    /// Percentage of the host's memory in use, if its capacity is known
+    pub fn memory_percent(&self, info: &HostInfo) -> Option<f64> {
+        (info.memory_total_bytes > 0)
+            .then(|| self.memory_used_bytes as f64 / info.memory_total_bytes as f64 * 100.0)
+    }
+}
+
+/// Sums the CPU and memory usage of the monitored containers per host
+pub fn host_usage(containers: &HashMap<ContainerKey, Container>) -> HashMap<HostId, HostUsage> {
+    let mut usage: HashMap<HostId, HostUsage> = HashMap::new();
+
+    for container in containers.values() {
+        let host = usage.entry(container.host_id.clone()).or_default();
+        // CPU is in percent of one core
+        host.cpu_cores += container.stats.cpu / 100.0;
+        host.memory_used_bytes += container.stats.memory_used_bytes.unwrap_or(0);
+    }
+
+    usage
+}
+
+/// Periodically fetches the host's `info` and disk usage and sends them via the event channel
+/// Stops when the event channel is closed
+///
+/// # Arguments
+/// * `host` - Docker host instance with identifier
+/// * `tx` - Event sender channel
+pub async fn poll_host_info(host: DockerHost, tx: EventSender) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    let mut disk_usage: Option<DiskUsage> = None;
+    let mut polls: u32 = 0;
+
+    loop {
+        interval.tick().await;
+
+        if polls.is_multiple_of(DISK_USAGE_POLLS) {
+            // Keep the previous value if the calculation fails
+            if let Ok(usage) = host.docker.df(None).await {
+                disk_usage = Some(DiskUsage::from_data_usage(&usage));
+            }
+        }
+        polls = polls.wrapping_add(1);
+
+        let started = Instant::now();
+        let result = host
+            .docker
+            .info()
+            .await
+            .map(|info| HostInfo {
+                latency: started.elapsed(),
+                disk_usage,
+                ..HostInfo::from_system_info(&info)
+            })
+            .map_err(|e| e.to_string());
+
+        if tx
+            .send(AppEvent::HostInfo(host.host_id.clone(), result))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ContainerStats;
+
+    fn container(host_id: &str, id: &str, cpu: f64, memory: Option<u64>) -> Container {
+        Container {
+            stats: ContainerStats {
+                cpu,
+                memory_used_bytes: memory,
+                ..Default::default()
+            },
+            ..Container::for_test(host_id, id)
+        }
+    }
+
+    #[test]
+    fn test_host_usage_sums_per_host() {
+        let containers: HashMap<_, _> = [
+            container("local", "a", 150.0, Some(100)),
+            container("local", "b", 50.0, None),
+            container("remote", "c", 25.0, Some(300)),
+        ]
+        .into_iter()
+        .map(|c| (ContainerKey::new(c.host_id.clone(), c.id.clone()), c))
+        .collect();
+
+        let usage = host_usage(&containers);
+
+        assert_eq!(usage["local"].cpu_cores, 2.0);
+        assert_eq!(usage["local"].memory_used_bytes, 100);
+        assert_eq!(usage["remote"].cpu_cores, 0.25);
+    }
+
+    #[test]
+    fn test_usage_percentages() {
+        let info = HostInfo {
+            cpus: 4,
+            memory_total_bytes: 1000,
+            ..Default::default()
+        };
+        let usage = HostUsage {
+            cpu_cores: 1.0,
+            memory_used_bytes: 250,
+        };
+
+        assert_eq!(usage.cpu_percent(&info), Some(25.0));
+        assert_eq!(usage.memory_percent(&info), Some(25.0));
+        assert_eq!(usage.cpu_percent(&HostInfo::default()), None);
+    }
+
+    #[test]
+    fn test_host_info_from_system_info() {
+        let info = HostInfo::from_system_info(&SystemInfo {
+            ncpu: Some(8),
+            mem_total: Some(16 * 1024 * 1024 * 1024),
+            containers_running: Some(3),
+            containers_stopped: Some(2),
+            server_version: Some("27.1.1".to_string()),
+            operating_system: Some("Ubuntu 24.04 LTS".to_string()),
+            ..Default::default()
+        });
+
+        assert_eq!(info.cpus, 8);
+        assert_eq!(info.containers_paused, 0);
+        assert_eq!(info.containers_stopped, 2);
+        assert_eq!(info.engine_version, "27.1.1");
+    }
+
+    #[test]
+    fn test_disk_usage_ignores_uncalculated_sizes() {
+        let usage = DiskUsage::from_data_usage(&SystemDataUsageResponse {
+            layers_size: Some(1000),
+            containers: Some(vec![
+                bollard::models::ContainerSummary {
+                    size_rw: Some(10),
+                    ..Default::default()
+                },
+                bollard::models::ContainerSummary {
+                    size_rw: Some(-1),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        });
+
+        assert_eq!(usage.images, 1000);
+        assert_eq!(usage.containers, 10);
+        assert_eq!(usage.total(), 1010);
+    }
+}
@@ -1,12 +1,78 @@
 use crossterm::event::{self, Event, KeyCode};
+use std::sync::{Arc, Condvar, Mutex};
 use std::time::Duration;
 
 use crate::types::{AppEvent, EventSender};
 
+/// Hands terminal input over between the keyboard worker and interactive sessions
+#[derive(Default)]
+pub struct InputPause {
+    state: Mutex<PauseState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct PauseState {
+    /// An interactive session wants the terminal's input
+    requested: bool,
+    /// The keyboard worker has stopped reading input (or has exited)
+    acknowledged: bool,
+}
+
+impl InputPause {
+    /// Stops the keyboard worker from reading input
+    /// Blocks until the worker has finished its current poll, so no keystroke meant
+    /// for the session can be consumed by the worker afterwards
+    pub fn pause(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.requested = true;
+        while !state.acknowledged {
+            state = self.changed.wait(state).unwrap();
+        }
+    }
+
+    /// Lets the keyboard worker read input again
+    pub fn resume(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.requested = false;
+        self.changed.notify_all();
+    }
+
+    /// Called by the worker between polls - parks it while a pause is requested
+    fn wait_while_paused(&self) {
+        let mut state = self.state.lock().unwrap();
+        if !state.requested {
+            return;
+        }
+
+        state.acknowledged = true;
+        self.changed.notify_all();
+        while state.requested {
+            state = self.changed.wait(state).unwrap();
+        }
+        state.acknowledged = false;
+    }
+
+    /// Called once the worker has exited, so pausing never waits for it
+    fn worker_exited(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.acknowledged = true;
+        self.changed.notify_all();
+    }
+}
+
 /// Polls for keyboard input and terminal events
 /// Sends events for various key presses and terminal resize
-pub fn keyboard_worker(tx: EventSender) {
+/// Stops reading input while paused so an interactive session can own the terminal
+pub fn keyboard_worker(tx: EventSender, pause: Arc<InputPause>) {
+    read_keyboard_events(&tx, &pause);
+    pause.worker_exited();
+}
+
+fn read_keyboard_events(tx: &EventSender, pause: &InputPause) {
     loop {
+        pause.wait_while_paused();
+
         // Poll every 200ms - humans won't notice the difference
         if event::poll(Duration::from_millis(200)).unwrap_or(false)
             && let Ok(event) = event::read()
@@ -19,10 +85,6 @@ pub fn keyboard_worker(tx: EventSender) {
                         let _ = tx.blocking_send(AppEvent::Quit);
                         break;
                     }
-                    KeyCode::Char('q') => {
-                        let _ = tx.blocking_send(AppEvent::Quit);
-                        break;
-                    }
                     KeyCode::Up => {
                         // Send both events - handler will decide based on view state
                         let _ = tx.blocking_send(AppEvent::SelectPrevious);
@@ -37,7 +99,18 @@ pub fn keyboard_worker(tx: EventSender) {
                         let _ = tx.blocking_send(AppEvent::EnterPressed);
                     }
                     KeyCode::Esc => {
-                        let _ = tx.blocking_send(AppEvent::ExitLogView);
+                        let _ = tx.blocking_send(AppEvent::EscapePressed);
+                    }
+                    KeyCode::Backspace => {
+                        let _ = tx.blocking_send(AppEvent::Backspace);
+                    }
+                    KeyCode::Char(c)
+                        if !key.modifiers.intersects(
+                            event::KeyModifiers::CONTROL | event::KeyModifiers::ALT,
+                        ) =>
+                    {
+                        // Handler maps characters to key bindings unless a prompt is capturing text
+                        let _ = tx.blocking_send(AppEvent::CharInput(c));
                     }
                     _ => {}
                 },
@@ -49,3 +122,40 @@ pub fn keyboard_worker(tx: EventSender) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_pause_waits_for_worker() {
+        let pause = Arc::new(InputPause::default());
+        let polls = Arc::new(AtomicUsize::new(0));
+
+        let worker = {
+            let (pause, polls) = (pause.clone(), polls.clone());
+            std::thread::spawn(move || {
+                for _ in 0..3 {
+                    pause.wait_while_paused();
+                    polls.fetch_add(1, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                pause.worker_exited();
+            })
+        };
+
+        pause.pause();
+        // The worker is parked, so it doesn't poll until resumed
+        let parked_at = polls.load(Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(polls.load(Ordering::SeqCst), parked_at);
+
+        pause.resume();
+        worker.join().unwrap();
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+
+        // Pausing after the worker exited doesn't block
+        pause.pause();
+    }
+}
@@ -1,9 +1,23 @@
 mod app_state;
+mod compose;
 mod config;
+mod diff;
 mod docker;
+mod events;
+mod exec;
+mod files;
+mod fleet;
+mod grouping;
+mod health;
+mod history;
+mod host;
 mod input;
 mod logs;
+mod prompt;
+mod shell;
+mod smoothing;
 mod stats;
+mod top;
 mod types;
 mod ui;
 
@@ -16,14 +30,17 @@ use crossterm::{
 use ratatui::{Terminal, backend::CrosstermBackend};
 use std::collections::HashMap;
 use std::io;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
 use app_state::AppState;
 use config::Config;
 use docker::{DockerHost, container_manager};
-use input::keyboard_worker;
-use types::AppEvent;
+use input::{InputPause, keyboard_worker};
+use shell::{run_attach_session, run_shell_session};
+use smoothing::{SmoothingConfig, SmoothingReceiver};
+use types::{AppEvent, InteractiveSession};
 use ui::{UiStyles, render_ui};
 
 /// Docker container monitoring TUI
@@ -84,6 +101,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Create event channel
     let (tx, mut rx) = mpsc::channel::<AppEvent>(1000);
 
+    // Smoothing can be switched at runtime, so stats streams watch for changes
+    let (smoothing_tx, smoothing_rx) = watch::channel(merged_config.smoothing);
+
     // Store DockerHost instances for log streaming
     let mut connected_hosts: HashMap<String, DockerHost> = HashMap::new();
 
@@ -99,7 +119,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 connected_hosts.insert(host_id.clone(), docker_host.clone());
 
                 // Spawn container manager for this host
-                spawn_container_manager(docker_host, tx.clone());
+                spawn_container_manager(docker_host, tx.clone(), smoothing_rx.clone());
             }
             Err(e) => {
                 // Log error but continue with other hosts
@@ -108,11 +128,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Shared flag used to pause the keyboard worker during interactive sessions
+    let input_pause = Arc::new(InputPause::default());
+
     // Spawn keyboard worker in blocking thread
-    spawn_keyboard_worker(tx.clone());
+    spawn_keyboard_worker(tx.clone(), input_pause.clone());
 
     // Run main event loop
-    run_event_loop(&mut terminal, &mut rx, tx.clone(), connected_hosts).await?;
+    run_event_loop(
+        &mut terminal,
+        &mut rx,
+        tx.clone(),
+        connected_hosts,
+        smoothing_tx,
+        &merged_config,
+        &input_pause,
+    )
+    .await?;
 
     // Restore terminal
     cleanup_terminal(&mut terminal)?;
@@ -192,16 +224,20 @@ fn cleanup_terminal(
 }
 
 /// Spawns the container manager task for a specific host
-fn spawn_container_manager(docker_host: DockerHost, tx: mpsc::Sender<AppEvent>) {
+fn spawn_container_manager(
+    docker_host: DockerHost,
+    tx: mpsc::Sender<AppEvent>,
+    smoothing: SmoothingReceiver,
+) {
     tokio::spawn(async move {
-        container_manager(docker_host, tx).await;
+        container_manager(docker_host, tx, smoothing).await;
     });
 }
 
 /// Spawns the keyboard input worker thread
-fn spawn_keyboard_worker(tx: mpsc::Sender<AppEvent>) {
+fn spawn_keyboard_worker(tx: mpsc::Sender<AppEvent>, pause: Arc<InputPause>) {
     std::thread::spawn(move || {
-        keyboard_worker(tx);
+        keyboard_worker(tx, pause);
     });
 }
 
@@ -211,8 +247,17 @@ async fn run_event_loop(
     rx: &mut mpsc::Receiver<AppEvent>,
     tx: mpsc::Sender<AppEvent>,
     connected_hosts: HashMap<String, DockerHost>,
+    smoothing_tx: watch::Sender<SmoothingConfig>,
+    config: &Config,
+    input_pause: &InputPause,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut state = AppState::new(connected_hosts, tx);
+    let mut state = AppState::new(
+        connected_hosts,
+        tx,
+        smoothing_tx,
+        config.history_length(),
+        config.fleet_label.clone(),
+    );
     let draw_interval = Duration::from_millis(500); // Refresh UI every 500ms
     let mut last_draw = std::time::Instant::now();
 
@@ -221,7 +266,14 @@ async fn run_event_loop(
 
     while !state.should_quit {
         // Wait for events with timeout - handles both throttling and waiting
-        let force_draw = process_events(rx, &mut state, draw_interval).await;
+        let mut force_draw = process_events(rx, &mut state, draw_interval).await;
+
+        // Hand the terminal over to an interactive session if one was requested
+        if let Some(session) = state.pending_session.take() {
+            state.status_message =
+                run_interactive_session(terminal, &state, &session, config, input_pause).await?;
+            force_draw = true;
+        }
 
         // Draw UI if forced (table structure changed) or if draw_interval has elapsed
         let should_draw = force_draw || last_draw.elapsed() >= draw_interval;
@@ -237,6 +289,55 @@ async fn run_event_loop(
     Ok(())
 }
 
+/// Suspends the TUI, runs an interactive shell or attach session, then restores the TUI
+/// Returns a status message describing the failure if the session could not be started
+async fn run_interactive_session(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    state: &AppState,
+    session: &InteractiveSession,
+    config: &Config,
+    input_pause: &InputPause,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let (InteractiveSession::Shell(container_key) | InteractiveSession::Attach(container_key)) =
+        session;
+
+    let Some(host) = state.connected_hosts.get(&container_key.host_id) else {
+        return Ok(Some(format!(
+            "Host '{}' is not connected",
+            container_key.host_id
+        )));
+    };
+
+    // Stop the keyboard worker from consuming input meant for the session, waiting
+    // for it to finish its current poll before the session starts reading
+    tokio::task::block_in_place(|| input_pause.pause());
+
+    // Leave the alternate screen but keep raw mode so keystrokes pass through unmodified
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    let container_id = &container_key.container_id;
+    let result = match session {
+        InteractiveSession::Shell(_) => {
+            run_shell_session(host, container_id, config.shell_command())
+                .await
+                .map_err(|e| format!("Shell failed: {}", e))
+        }
+        InteractiveSession::Attach(_) => {
+            run_attach_session(host, container_id, config.detach_keys())
+                .await
+                .map_err(|e| format!("Attach failed: {}", e))
+        }
+    };
+
+    // Restore the TUI and force a full redraw
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    terminal.clear()?;
+    input_pause.resume();
+
+    Ok(result.err())
+}
+
 /// Processes all pending events from the event channel
 /// Waits with timeout for at least one event, then drains all pending events
 /// Returns true if a force draw is needed (table structure changed)
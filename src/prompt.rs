@@ -0,0 +1,106 @@
+use crate::types::ContainerKey;
+
+/// What happens when a prompt is submitted
+#[derive(Clone, Debug, PartialEq)]
+pub enum PromptAction {
+    /// Run the input as a command in the container
+    Exec,
+    /// Download a container path to the local destination typed in the input
+    Download { path: String },
+    /// Open the container path typed in the input in the file browser
+    OpenPath,
+}
+
+/// Single-line text prompt state while the user is typing
+#[derive(Clone, Debug)]
+pub struct Prompt {
+    /// Container the prompt applies to
+    pub key: ContainerKey,
+    /// Action performed with the input on submit
+    pub action: PromptAction,
+    /// Text typed so far
+    pub input: String,
+    /// Position in the command history when browsing with ↑/↓
+    pub history_index: Option<usize>,
+}
+
+impl Prompt {
+    pub fn new(key: ContainerKey, action: PromptAction, input: String) -> Self {
+        Self {
+            key,
+            action,
+            input,
+            history_index: None,
+        }
+    }
+
+    /// Moves to the previous (older) history entry and loads it into the input
+    pub fn history_previous(&mut self, history: &[String]) {
+        if history.is_empty() {
+            return;
+        }
+
+        let index = match self.history_index {
+            Some(idx) => idx.saturating_sub(1),
+            None => history.len() - 1,
+        };
+
+        self.history_index = Some(index);
+        self.input = history[index].clone();
+    }
+
+    /// Moves to the next (newer) history entry, clearing the input past the newest one
+    pub fn history_next(&mut self, history: &[String]) {
+        let Some(idx) = self.history_index else {
+            return;
+        };
+
+        if idx + 1 < history.len() {
+            self.history_index = Some(idx + 1);
+            self.input = history[idx + 1].clone();
+        } else {
+            self.history_index = None;
+            self.input.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_history_navigation() {
+        let commands = vec!["env".to_string(), "ls".to_string()];
+        let key = ContainerKey::new("local".into(), "abc".into());
+        let mut prompt = Prompt::new(key, PromptAction::Exec, String::new());
+
+        prompt.history_previous(&commands);
+        assert_eq!(prompt.input, "ls");
+
+        prompt.history_previous(&commands);
+        assert_eq!(prompt.input, "env");
+
+        // Stays on the oldest entry
+        prompt.history_previous(&commands);
+        assert_eq!(prompt.input, "env");
+
+        prompt.history_next(&commands);
+        assert_eq!(prompt.input, "ls");
+
+        // Moving past the newest entry clears the input
+        prompt.history_next(&commands);
+        assert_eq!(prompt.input, "");
+        assert_eq!(prompt.history_index, None);
+    }
+
+    #[test]
+    fn test_prompt_history_empty() {
+        let key = ContainerKey::new("local".into(), "abc".into());
+        let mut prompt = Prompt::new(key, PromptAction::Exec, "ls".to_string());
+
+        prompt.history_previous(&[]);
+        assert_eq!(prompt.input, "ls");
+        assert_eq!(prompt.history_index, None);
+    }
+}
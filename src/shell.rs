@@ -0,0 +1,537 @@
+use bollard::container::LogOutput;
+use bollard::errors::Error as DockerError;
+use bollard::exec::{CreateExecOptions, ResizeExecOptions, StartExecOptions, StartExecResults};
+use bollard::query_parameters::{
+    AttachContainerOptions, InspectContainerOptions, ResizeContainerTTYOptions,
+};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures_util::stream::{Stream, StreamExt};
+use std::io::Write;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+use crate::docker::DockerHost;
+
+/// Input captured from the local terminal and forwarded to a remote TTY
+enum TtyInput {
+    /// Raw bytes to write to the remote stdin
+    Bytes(Vec<u8>),
+    /// Local terminal was resized to (width, height)
+    Resize(u16, u16),
+}
+
+/// Remote process whose TTY receives resize requests
+enum TtyTarget<'a> {
+    /// Exec instance ID
+    Exec(&'a str),
+    /// Container ID (main process)
+    Container(&'a str),
+}
+
+/// Options controlling how local input is forwarded to the remote process
+struct PumpOptions {
+    /// Detach sequence that ends the session without stopping the remote process
+    detach: Option<DetachMatcher>,
+    /// Translate Enter (\r) to \n for processes without a TTY
+    translate_enter: bool,
+    /// Message written to the terminal before any remote output
+    banner: Option<String>,
+}
+
+/// Runs an interactive shell in a container via the exec API
+///
+/// The caller must suspend the TUI (leave the alternate screen and pause the keyboard worker)
+/// before calling this; raw mode must stay enabled so keystrokes are passed through unmodified.
+/// Returns once the shell process exits.
+///
+/// # Arguments
+/// * `host` - Docker host instance the container runs on
+/// * `container_id` - Container ID (truncated IDs are accepted by the Docker API)
+/// * `cmd` - Shell command and arguments to execute
+pub async fn run_shell_session(
+    host: &DockerHost,
+    container_id: &str,
+    cmd: Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let exec = host
+        .docker
+        .create_exec(
+            container_id,
+            CreateExecOptions {
+                attach_stdin: Some(true),
+                attach_stdout: Some(true),
+                attach_stderr: Some(true),
+                tty: Some(true),
+                cmd: Some(cmd),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let start_options = StartExecOptions {
+        detach: false,
+        tty: true,
+        output_capacity: None,
+    };
+
+    let StartExecResults::Attached { output, input } = host
+        .docker
+        .start_exec(&exec.id, Some(start_options))
+        .await?
+    else {
+        return Err("Exec session started detached".into());
+    };
+
+    let options = PumpOptions {
+        detach: None,
+        translate_enter: false,
+        banner: None,
+    };
+
+    pump_tty(host, TtyTarget::Exec(&exec.id), output, input, options).await;
+
+    Ok(())
+}
+
+/// Attaches to a container's main process like `docker attach`
+///
+/// Same terminal requirements as [`run_shell_session`]. Returns when the detach key
+/// sequence is typed or the container's output stream ends; detaching leaves the
+/// container running.
+///
+/// # Arguments
+/// * `host` - Docker host instance the container runs on
+/// * `container_id` - Container ID (truncated IDs are accepted by the Docker API)
+/// * `detach_keys` - Detach sequence in Docker format (e.g. "ctrl-p,ctrl-q")
+pub async fn run_attach_session(
+    host: &DockerHost,
+    container_id: &str,
+    detach_keys: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let detach_sequence = parse_detach_keys(detach_keys)
+        .ok_or_else(|| format!("Invalid detach keys: '{}'", detach_keys))?;
+
+    // Only forward stdin if the container keeps it open, and only send raw keys to a TTY
+    let inspect = host
+        .docker
+        .inspect_container(container_id, None::<InspectContainerOptions>)
+        .await?;
+    let config = inspect.config.unwrap_or_default();
+    let has_tty = config.tty.unwrap_or(false);
+    let open_stdin = config.open_stdin.unwrap_or(false);
+
+    let attach = host
+        .docker
+        .attach_container(
+            container_id,
+            Some(AttachContainerOptions {
+                detach_keys: Some(detach_keys.to_string()),
+                logs: false,
+                stream: true,
+                stdin: open_stdin,
+                stdout: true,
+                stderr: true,
+            }),
+        )
+        .await?;
+
+    let options = PumpOptions {
+        detach: Some(DetachMatcher::new(detach_sequence)),
+        translate_enter: !has_tty,
+        // Local output isn't echoed by the remote end without a TTY, so tell the user how to leave
+        banner: (!has_tty).then(|| {
+            format!(
+                "Attached to {} - press {} to detach\r\n",
+                container_id, detach_keys
+            )
+        }),
+    };
+
+    pump_tty(
+        host,
+        TtyTarget::Container(container_id),
+        attach.output,
+        attach.input,
+        options,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Forwards remote output to stdout and local input to the remote process until
+/// the output stream ends or the detach sequence is typed
+async fn pump_tty(
+    host: &DockerHost,
+    target: TtyTarget<'_>,
+    mut output: Pin<Box<dyn Stream<Item = Result<LogOutput, DockerError>> + Send>>,
+    mut input: Pin<Box<dyn AsyncWrite + Send>>,
+    mut options: PumpOptions,
+) {
+    // Match the remote TTY to the local terminal before the process redraws
+    if let Ok((width, height)) = crossterm::terminal::size() {
+        resize_tty(host, &target, width, height).await;
+    }
+
+    let mut stdout = std::io::stdout();
+
+    if let Some(banner) = &options.banner
+        && stdout
+            .write_all(banner.as_bytes())
+            .and_then(|_| stdout.flush())
+            .is_err()
+    {
+        return;
+    }
+
+    let (input_tx, mut input_rx) = mpsc::channel::<TtyInput>(64);
+    let done = Arc::new(AtomicBool::new(false));
+    let reader = spawn_input_reader(input_tx, done.clone());
+
+    loop {
+        tokio::select! {
+            chunk = output.next() => match chunk {
+                Some(Ok(log_output)) => {
+                    let bytes = log_output.into_bytes();
+                    if stdout.write_all(&bytes).and_then(|_| stdout.flush()).is_err() {
+                        break;
+                    }
+                }
+                // Stream ended or failed - the remote process has exited or detached
+                _ => break,
+            },
+            Some(tty_input) = input_rx.recv() => match tty_input {
+                TtyInput::Bytes(bytes) => {
+                    let (mut bytes, detached) = match &mut options.detach {
+                        Some(matcher) => matcher.feed(&bytes),
+                        None => (bytes, false),
+                    };
+
+                    if options.translate_enter {
+                        bytes = bytes
+                            .into_iter()
+                            .map(|b| if b == b'\r' { b'\n' } else { b })
+                            .collect();
+                    }
+
+                    if !bytes.is_empty() && input.write_all(&bytes).await.is_err() {
+                        break;
+                    }
+                    let _ = input.flush().await;
+
+                    if detached {
+                        break;
+                    }
+                }
+                TtyInput::Resize(width, height) => {
+                    resize_tty(host, &target, width, height).await;
+                }
+            },
+        }
+    }
+
+    // Stop the input reader so the keyboard worker can take over again. Dropping the
+    // receiver fails a send the reader may be blocked on when the channel is full.
+    done.store(true, Ordering::Relaxed);
+    drop(input_rx);
+    let _ = reader.await;
+}
+
+/// Resizes the remote TTY; errors are ignored since processes without a TTY reject resizes
+async fn resize_tty(host: &DockerHost, target: &TtyTarget<'_>, width: u16, height: u16) {
+    let _ = match target {
+        TtyTarget::Exec(exec_id) => {
+            host.docker
+                .resize_exec(exec_id, ResizeExecOptions { width, height })
+                .await
+        }
+        TtyTarget::Container(container_id) => {
+            host.docker
+                .resize_container_tty(
+                    container_id,
+                    ResizeContainerTTYOptions {
+                        w: width as i32,
+                        h: height as i32,
+                    },
+                )
+                .await
+        }
+    };
+}
+
+/// Spawns a blocking task that reads terminal events and forwards them as TTY input
+/// Polls with a short timeout so it can stop promptly once `done` is set
+fn spawn_input_reader(
+    tx: mpsc::Sender<TtyInput>,
+    done: Arc<AtomicBool>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::task::spawn_blocking(move || {
+        while !done.load(Ordering::Relaxed) {
+            if !event::poll(Duration::from_millis(50)).unwrap_or(false) {
+                continue;
+            }
+
+            let tty_input = match event::read() {
+                Ok(Event::Key(key)) => key_event_to_bytes(&key).map(TtyInput::Bytes),
+                Ok(Event::Paste(text)) => Some(TtyInput::Bytes(text.into_bytes())),
+                Ok(Event::Resize(width, height)) => Some(TtyInput::Resize(width, height)),
+                _ => None,
+            };
+
+            if let Some(tty_input) = tty_input
+                && tx.blocking_send(tty_input).is_err()
+            {
+                break;
+            }
+        }
+    })
+}
+
+/// Matches typed input against a detach sequence, holding back bytes of a partial match
+struct DetachMatcher {
+    sequence: Vec<u8>,
+    matched: usize,
+}
+
+impl DetachMatcher {
+    fn new(sequence: Vec<u8>) -> Self {
+        Self {
+            sequence,
+            matched: 0,
+        }
+    }
+
+    /// Feeds typed bytes and returns the bytes to forward and whether the sequence completed
+    /// Bytes held back for a partial match are released once the match breaks
+    fn feed(&mut self, bytes: &[u8]) -> (Vec<u8>, bool) {
+        let mut forward = Vec::with_capacity(bytes.len());
+
+        for &byte in bytes {
+            if byte == self.sequence[self.matched] {
+                self.matched += 1;
+            } else {
+                forward.extend_from_slice(&self.sequence[..self.matched]);
+                if byte == self.sequence[0] {
+                    self.matched = 1;
+                } else {
+                    self.matched = 0;
+                    forward.push(byte);
+                }
+            }
+
+            if self.matched == self.sequence.len() {
+                self.matched = 0;
+                return (forward, true);
+            }
+        }
+
+        (forward, false)
+    }
+}
+
+/// Parses a Docker detach key specification (e.g. "ctrl-p,ctrl-q") into bytes
+/// Each comma-separated key is a single character or `ctrl-<value>`
+pub fn parse_detach_keys(spec: &str) -> Option<Vec<u8>> {
+    let keys: Option<Vec<u8>> = spec
+        .split(',')
+        .map(|key| match key.strip_prefix("ctrl-") {
+            Some(value) if value.chars().count() == 1 => {
+                control_byte(value.chars().next()?.to_ascii_lowercase())
+            }
+            Some(_) => None,
+            None if key.len() == 1 && key.is_ascii() => Some(key.as_bytes()[0]),
+            None => None,
+        })
+        .collect();
+
+    keys.filter(|k| !k.is_empty())
+}
+
+/// Converts a key event into the byte sequence a terminal would send for it
+/// Returns None for key releases and keys that have no terminal encoding
+pub fn key_event_to_bytes(key: &KeyEvent) -> Option<Vec<u8>> {
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+
+    let bytes = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            vec![control_byte(c)?]
+        }
+        KeyCode::Char(c) => {
+            let mut buf = [0u8; 4];
+            c.encode_utf8(&mut buf).as_bytes().to_vec()
+        }
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::F(n) => function_key_bytes(n)?,
+        _ => return None,
+    };
+
+    // Alt is sent as an ESC prefix
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        let mut prefixed = vec![0x1b];
+        prefixed.extend(bytes);
+        Some(prefixed)
+    } else {
+        Some(bytes)
+    }
+}
+
+/// Maps Ctrl+<char> to its ASCII control code
+fn control_byte(c: char) -> Option<u8> {
+    match c {
+        'a'..='z' => Some(c as u8 - b'a' + 1),
+        'A'..='Z' => Some(c as u8 - b'A' + 1),
+        '@' | ' ' => Some(0x00),
+        '[' => Some(0x1b),
+        '\\' => Some(0x1c),
+        ']' => Some(0x1d),
+        '^' => Some(0x1e),
+        '_' => Some(0x1f),
+        _ => None,
+    }
+}
+
+/// Returns the xterm escape sequence for function keys F1-F12
+fn function_key_bytes(n: u8) -> Option<Vec<u8>> {
+    let sequence: &[u8] = match n {
+        1 => b"\x1bOP",
+        2 => b"\x1bOQ",
+        3 => b"\x1bOR",
+        4 => b"\x1bOS",
+        5 => b"\x1b[15~",
+        6 => b"\x1b[17~",
+        7 => b"\x1b[18~",
+        8 => b"\x1b[19~",
+        9 => b"\x1b[20~",
+        10 => b"\x1b[21~",
+        11 => b"\x1b[23~",
+        12 => b"\x1b[24~",
+        _ => return None,
+    };
+    Some(sequence.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_plain_characters() {
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::Char('a'), KeyModifiers::NONE)),
+            Some(b"a".to_vec())
+        );
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::Char('é'), KeyModifiers::NONE)),
+            Some("é".as_bytes().to_vec())
+        );
+    }
+
+    #[test]
+    fn test_control_characters() {
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(vec![0x03])
+        );
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            Some(vec![0x04])
+        );
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::Char('['), KeyModifiers::CONTROL)),
+            Some(vec![0x1b])
+        );
+    }
+
+    #[test]
+    fn test_alt_prefixes_escape() {
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::Char('b'), KeyModifiers::ALT)),
+            Some(b"\x1bb".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_special_keys() {
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::Enter, KeyModifiers::NONE)),
+            Some(b"\r".to_vec())
+        );
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::Backspace, KeyModifiers::NONE)),
+            Some(vec![0x7f])
+        );
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::Up, KeyModifiers::NONE)),
+            Some(b"\x1b[A".to_vec())
+        );
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::F(5), KeyModifiers::NONE)),
+            Some(b"\x1b[15~".to_vec())
+        );
+        assert_eq!(
+            key_event_to_bytes(&key(KeyCode::F(13), KeyModifiers::NONE)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_detach_keys() {
+        assert_eq!(parse_detach_keys("ctrl-p,ctrl-q"), Some(vec![0x10, 0x11]));
+        assert_eq!(parse_detach_keys("ctrl-a,x"), Some(vec![0x01, b'x']));
+        assert_eq!(parse_detach_keys("ctrl-@"), Some(vec![0x00]));
+        assert_eq!(parse_detach_keys(""), None);
+        assert_eq!(parse_detach_keys("ctrl-pq"), None);
+        assert_eq!(parse_detach_keys("alt-p"), None);
+    }
+
+    #[test]
+    fn test_detach_matcher_completes_sequence() {
+        let mut matcher = DetachMatcher::new(vec![0x10, 0x11]);
+
+        assert_eq!(matcher.feed(b"ls\r"), (b"ls\r".to_vec(), false));
+        assert_eq!(matcher.feed(&[0x10]), (vec![], false));
+        assert_eq!(matcher.feed(&[0x11]), (vec![], true));
+    }
+
+    #[test]
+    fn test_detach_matcher_releases_partial_match() {
+        let mut matcher = DetachMatcher::new(vec![0x10, 0x11]);
+
+        assert_eq!(matcher.feed(&[0x10]), (vec![], false));
+        assert_eq!(matcher.feed(b"a"), (vec![0x10, b'a'], false));
+
+        // A repeated first key restarts the match
+        assert_eq!(matcher.feed(&[0x10, 0x10, 0x11]), (vec![0x10], true));
+    }
+
+    #[test]
+    fn test_key_release_ignored() {
+        let mut release = key(KeyCode::Char('a'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        assert_eq!(key_event_to_bytes(&release), None);
+    }
+}
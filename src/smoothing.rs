@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use tokio::sync::watch;
+
+/// How a metric's raw samples are smoothed before being displayed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Smoothing {
+    /// Raw values, showing every spike
+    None,
+    /// Exponential moving average: smoothed = alpha * new + (1 - alpha) * previous
+    /// Higher alpha is more responsive, lower alpha is smoother
+    Ema { alpha: f64 },
+    /// Mean of the last `window` samples
+    Mean { window: usize },
+    /// Maximum of the last `window` samples, so short bursts stay visible
+    Max { window: usize },
+}
+
+impl Default for Smoothing {
+    fn default() -> Self {
+        // 0.3 provides good balance between responsiveness and smoothness
+        Smoothing::Ema { alpha: 0.3 }
+    }
+}
+
+impl fmt::Display for Smoothing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Smoothing::None => write!(f, "none"),
+            Smoothing::Ema { alpha } => write!(f, "ema {}", alpha),
+            Smoothing::Mean { window } => write!(f, "mean {}", window),
+            Smoothing::Max { window } => write!(f, "max {}", window),
+        }
+    }
+}
+
+/// Smoothing for each group of metrics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmoothingConfig {
+    pub cpu: Smoothing,
+    pub memory: Smoothing,
+    /// Network transmit and receive rates
+    pub network: Smoothing,
+    /// Block device read and write rates
+    pub block_io: Smoothing,
+}
+
+impl SmoothingConfig {
+    /// Uses the same smoothing for every metric
+    pub fn uniform(smoothing: Smoothing) -> Self {
+        Self {
+            cpu: smoothing,
+            memory: smoothing,
+            network: smoothing,
+            block_io: smoothing,
+        }
+    }
+}
+
+/// Receives smoothing changes made at runtime
+pub type SmoothingReceiver = watch::Receiver<SmoothingConfig>;
+
+/// Presets cycled through at runtime with 'm'; None restores the configured smoothing
+pub const SMOOTHING_PRESETS: [Option<Smoothing>; 5] = [
+    None,
+    Some(Smoothing::None),
+    Some(Smoothing::Ema { alpha: 0.3 }),
+    Some(Smoothing::Mean { window: 5 }),
+    Some(Smoothing::Max { window: 5 }),
+];
+
+/// Applies a smoothing mode to a stream of samples
+#[derive(Debug, Clone)]
+pub struct Smoother {
+    smoothing: Smoothing,
+    previous: Option<f64>,
+    window: VecDeque<f64>,
+}
+
+impl Smoother {
+    pub fn new(smoothing: Smoothing) -> Self {
+        Self {
+            smoothing,
+            previous: None,
+            window: VecDeque::new(),
+        }
+    }
+
+    /// Adds a sample and returns the smoothed value
+    pub fn update(&mut self, value: f64) -> f64 {
+        let smoothed = match self.smoothing {
+            Smoothing::None => value,
+            Smoothing::Ema { alpha } => {
+                let alpha = alpha.clamp(0.0, 1.0);
+                match self.previous {
+                    Some(prev) => alpha * value + (1.0 - alpha) * prev,
+                    None => value, // First value, no smoothing
+                }
+            }
+            Smoothing::Mean { window } => {
+                self.push_window(value, window);
+                self.window.iter().sum::<f64>() / self.window.len() as f64
+            }
+            Smoothing::Max { window } => {
+                self.push_window(value, window);
+                self.window.iter().copied().fold(f64::MIN, f64::max)
+            }
+        };
+
+        self.previous = Some(smoothed);
+        smoothed
+    }
+
+    fn push_window(&mut self, value: f64, size: usize) {
+        self.window.push_back(value);
+        while self.window.len() > size.max(1) {
+            self.window.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(smoothing: Smoothing, values: &[f64]) -> Vec<f64> {
+        let mut smoother = Smoother::new(smoothing);
+        values.iter().map(|&v| smoother.update(v)).collect()
+    }
+
+    #[test]
+    fn test_none_passes_values_through() {
+        assert_eq!(
+            run(Smoothing::None, &[1.0, 100.0, 2.0]),
+            vec![1.0, 100.0, 2.0]
+        );
+    }
+
+    #[test]
+    fn test_ema() {
+        let values = run(Smoothing::Ema { alpha: 0.5 }, &[10.0, 20.0, 20.0]);
+        assert_eq!(values, vec![10.0, 15.0, 17.5]);
+    }
+
+    #[test]
+    fn test_mean_over_window() {
+        let values = run(Smoothing::Mean { window: 2 }, &[10.0, 20.0, 40.0]);
+        assert_eq!(values, vec![10.0, 15.0, 30.0]);
+    }
+
+    #[test]
+    fn test_max_over_window() {
+        let values = run(Smoothing::Max { window: 2 }, &[10.0, 50.0, 20.0, 5.0]);
+        assert_eq!(values, vec![10.0, 50.0, 50.0, 20.0]);
+    }
+
+    #[test]
+    fn test_zero_window_behaves_like_none() {
+        let values = run(Smoothing::Mean { window: 0 }, &[10.0, 20.0]);
+        assert_eq!(values, vec![10.0, 20.0]);
+    }
+
+    #[test]
+    fn test_yaml_deserialization() {
+        let yaml = r#"
+cpu:
+  mode: none
+network:
+  mode: max
+  window: 5
+block_io:
+  mode: ema
+  alpha: 0.5
+"#;
+        let config: SmoothingConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(config.cpu, Smoothing::None);
+        assert_eq!(config.memory, Smoothing::default());
+        assert_eq!(config.network, Smoothing::Max { window: 5 });
+        assert_eq!(config.block_io, Smoothing::Ema { alpha: 0.5 });
+    }
+}
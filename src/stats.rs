@@ -1,88 +1,132 @@
-use bollard::models::ContainerStatsResponse;
-use bollard::query_parameters::StatsOptions;
+use bollard::models::{
+    ContainerMemoryStats, ContainerNetworkStats, ContainerStatsResponse, HostConfig,
+};
+use bollard::query_parameters::{InspectContainerOptions, StatsOptions};
 use futures_util::stream::StreamExt;
+use std::collections::HashMap;
 use std::time::Instant;
 
 use crate::docker::DockerHost;
-use crate::types::{AppEvent, ContainerKey, ContainerStats, EventSender};
+use crate::smoothing::{Smoother, SmoothingConfig, SmoothingReceiver};
+use crate::types::{
+    AppEvent, ContainerKey, ContainerStats, EventSender, InterfaceStats, MemoryBreakdown,
+};
 
 /// Streams stats for a single container and sends updates via the event channel
 ///
-/// Uses exponential decay smoothing to reduce noise in stats:
-/// smoothed = alpha * new_value + (1 - alpha) * previous_smoothed
+/// CPU, memory, network and block I/O values are smoothed to reduce noise, using the
+/// smoothing configured per metric. Smoothing changes take effect on the next sample.
 ///
 /// # Arguments
 /// * `host` - Docker host instance with identifier
 /// * `truncated_id` - Truncated container ID (12 chars) - Docker API accepts partial IDs
 /// * `tx` - Event sender channel
-pub async fn stream_container_stats(host: DockerHost, truncated_id: String, tx: EventSender) {
+/// * `smoothing` - Receiver of the smoothing to apply
+pub async fn stream_container_stats(
+    host: DockerHost,
+    truncated_id: String,
+    tx: EventSender,
+    mut smoothing: SmoothingReceiver,
+) {
     let stats_options = StatsOptions {
         stream: true,
         one_shot: false,
     };
 
-    let mut stats_stream = host.docker.stats(&truncated_id, Some(stats_options));
+    // Unconstrained containers report the host's memory as their limit, so check
+    // the configured limits once to tell the two apart
+    let host_config = host
+        .docker
+        .inspect_container(&truncated_id, None::<InspectContainerOptions>)
+        .await
+        .ok()
+        .and_then(|inspect| inspect.host_config);
+    let has_memory_limit = host_config
+        .as_ref()
+        .is_none_or(|config| config.memory.is_some_and(|memory| memory > 0));
+    let configured_cpu_limit = host_config.as_ref().and_then(cpu_limit_cores);
 
-    // Smoothing factor: higher alpha = more responsive, lower alpha = smoother
-    // 0.3 provides good balance between responsiveness and smoothness
-    const ALPHA: f64 = 0.3;
+    let mut stats_stream = host.docker.stats(&truncated_id, Some(stats_options));
 
-    let mut smoothed_cpu: Option<f64> = None;
-    let mut smoothed_memory: Option<f64> = None;
-    let mut smoothed_net_tx: Option<f64> = None;
-    let mut smoothed_net_rx: Option<f64> = None;
+    let mut smoothers = MetricSmoothers::new(*smoothing.borrow_and_update());
 
-    // Track previous network stats for rate calculation
+    // Track previous network and block I/O stats for rate calculation
     let mut prev_net_tx: Option<u64> = None;
     let mut prev_net_rx: Option<u64> = None;
+    let mut prev_block_read: Option<u64> = None;
+    let mut prev_block_write: Option<u64> = None;
+    let mut prev_throttling: Option<ThrottlingCounters> = None;
+    let mut prev_networks: Option<HashMap<String, ContainerNetworkStats>> = None;
     let mut prev_timestamp: Option<Instant> = None;
 
     while let Some(result) = stats_stream.next().await {
         match result {
             Ok(stats) => {
                 let cpu_percent = calculate_cpu_percentage(&stats);
+                let online_cpus = stats
+                    .cpu_stats
+                    .as_ref()
+                    .and_then(|cs| cs.online_cpus)
+                    .unwrap_or(1)
+                    .max(1) as f64;
+                let cpu_limit_cores =
+                    configured_cpu_limit.map_or(online_cpus, |limit| limit.min(online_cpus));
+                let (pids_current, pids_limit) = extract_pids(&stats);
+                let (memory_used_bytes, memory_limit_bytes) = extract_memory_bytes(&stats);
+                let memory_limit_bytes = memory_limit_bytes.filter(|_| has_memory_limit);
                 let memory_percent = calculate_memory_percentage(&stats);
                 let (net_tx_rate, net_rx_rate) =
                     calculate_network_rates(&stats, prev_net_tx, prev_net_rx, prev_timestamp);
+                let (cpu_throttled_ratio, cpu_throttled_secs_per_sec) =
+                    calculate_throttling(&stats, prev_throttling, prev_timestamp);
+                let (block_read_rate, block_write_rate) = calculate_block_io_rates(
+                    &stats,
+                    prev_block_read,
+                    prev_block_write,
+                    prev_timestamp,
+                );
 
-                // Update previous network values for next iteration
+                // Update previous network and block I/O values for next iteration
                 let (tx_bytes, rx_bytes) = extract_network_bytes(&stats);
                 prev_net_tx = tx_bytes;
                 prev_net_rx = rx_bytes;
+                let (read_bytes, write_bytes) = extract_block_io_bytes(&stats);
+                prev_block_read = read_bytes;
+                prev_block_write = write_bytes;
+                prev_throttling = extract_throttling(&stats);
+                let network_interfaces =
+                    calculate_interface_stats(&stats, prev_networks.as_ref(), prev_timestamp);
+                prev_networks = stats.networks.clone();
                 prev_timestamp = Some(Instant::now());
 
-                // Apply exponential moving average
-                let cpu = match smoothed_cpu {
-                    Some(prev) => ALPHA * cpu_percent + (1.0 - ALPHA) * prev,
-                    None => cpu_percent, // First value, no smoothing
-                };
-
-                let memory = match smoothed_memory {
-                    Some(prev) => ALPHA * memory_percent + (1.0 - ALPHA) * prev,
-                    None => memory_percent, // First value, no smoothing
-                };
-
-                let network_tx_bytes_per_sec = match smoothed_net_tx {
-                    Some(prev) => ALPHA * net_tx_rate + (1.0 - ALPHA) * prev,
-                    None => net_tx_rate,
-                };
-
-                let network_rx_bytes_per_sec = match smoothed_net_rx {
-                    Some(prev) => ALPHA * net_rx_rate + (1.0 - ALPHA) * prev,
-                    None => net_rx_rate,
-                };
+                // Start over with the new smoothing when it was switched
+                if smoothing.has_changed().unwrap_or(false) {
+                    smoothers = MetricSmoothers::new(*smoothing.borrow_and_update());
+                }
 
-                // Update smoothed values for next iteration
-                smoothed_cpu = Some(cpu);
-                smoothed_memory = Some(memory);
-                smoothed_net_tx = Some(network_tx_bytes_per_sec);
-                smoothed_net_rx = Some(network_rx_bytes_per_sec);
+                let cpu = smoothers.cpu.update(cpu_percent);
+                let memory = smoothers.memory.update(memory_percent);
+                let network_tx_bytes_per_sec = smoothers.network_tx.update(net_tx_rate);
+                let network_rx_bytes_per_sec = smoothers.network_rx.update(net_rx_rate);
+                let block_read_bytes_per_sec = smoothers.block_read.update(block_read_rate);
+                let block_write_bytes_per_sec = smoothers.block_write.update(block_write_rate);
 
                 let stats = ContainerStats {
                     cpu,
+                    cpu_limit_cores,
+                    cpu_throttled_ratio,
+                    cpu_throttled_secs_per_sec,
                     memory,
                     network_tx_bytes_per_sec,
                     network_rx_bytes_per_sec,
+                    network_interfaces,
+                    block_read_bytes_per_sec,
+                    block_write_bytes_per_sec,
+                    memory_used_bytes,
+                    memory_limit_bytes,
+                    pids_current,
+                    pids_limit,
+                    memory_breakdown: extract_memory_breakdown(&stats),
                 };
 
                 let key = ContainerKey::new(host.host_id.clone(), truncated_id.clone());
@@ -99,6 +143,29 @@ pub async fn stream_container_stats(host: DockerHost, truncated_id: String, tx:
     let _ = tx.send(AppEvent::ContainerDestroyed(key)).await;
 }
 
+/// Smoothing state of every smoothed metric of a container
+struct MetricSmoothers {
+    cpu: Smoother,
+    memory: Smoother,
+    network_tx: Smoother,
+    network_rx: Smoother,
+    block_read: Smoother,
+    block_write: Smoother,
+}
+
+impl MetricSmoothers {
+    fn new(config: SmoothingConfig) -> Self {
+        Self {
+            cpu: Smoother::new(config.cpu),
+            memory: Smoother::new(config.memory),
+            network_tx: Smoother::new(config.network),
+            network_rx: Smoother::new(config.network),
+            block_read: Smoother::new(config.block_io),
+            block_write: Smoother::new(config.block_io),
+        }
+    }
+}
+
 /// Calculates CPU usage percentage from container stats
 pub fn calculate_cpu_percentage(stats: &ContainerStatsResponse) -> f64 {
     let cpu_stats = match &stats.cpu_stats {
@@ -133,14 +200,88 @@ pub fn calculate_cpu_percentage(stats: &ContainerStatsResponse) -> f64 {
     }
 }
 
+/// Returns the number of CPU cores a container is limited to, if it has a CPU limit
+/// `--cpus` sets NanoCpus, while `--cpu-quota`/`--cpu-period` set the CFS quota directly
+fn cpu_limit_cores(config: &HostConfig) -> Option<f64> {
+    if let Some(nano_cpus) = config.nano_cpus.filter(|&n| n > 0) {
+        return Some(nano_cpus as f64 / 1e9);
+    }
+
+    let quota = config.cpu_quota.filter(|&q| q > 0)?;
+    // The kernel's default period is 100ms when none is configured
+    let period = config.cpu_period.filter(|&p| p > 0).unwrap_or(100_000);
+
+    Some(quota as f64 / period as f64)
+}
+
+/// Cumulative CFS throttling counters of a container
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ThrottlingCounters {
+    /// Enforcement periods that have elapsed
+    periods: u64,
+    /// Periods in which the container was throttled
+    throttled_periods: u64,
+    /// Total time the container was throttled, in nanoseconds
+    throttled_time: u64,
+}
+
+/// Extracts CPU throttling counters from container stats
+fn extract_throttling(stats: &ContainerStatsResponse) -> Option<ThrottlingCounters> {
+    let data = stats.cpu_stats.as_ref()?.throttling_data.as_ref()?;
+
+    Some(ThrottlingCounters {
+        periods: data.periods.unwrap_or(0),
+        throttled_periods: data.throttled_periods.unwrap_or(0),
+        throttled_time: data.throttled_time.unwrap_or(0),
+    })
+}
+
+/// Calculates CPU throttling since the previous sample
+///
+/// Returns the share of enforcement periods in which the container was throttled
+/// (0.0 - 1.0) and the seconds spent throttled per second. Containers without a
+/// CPU quota never have any periods and always report zero.
+fn calculate_throttling(
+    stats: &ContainerStatsResponse,
+    prev: Option<ThrottlingCounters>,
+    prev_time: Option<Instant>,
+) -> (f64, f64) {
+    let (current, prev, prev_time) = match (extract_throttling(stats), prev, prev_time) {
+        (Some(current), Some(prev), Some(time)) => (current, prev, time),
+        _ => return (0.0, 0.0),
+    };
+
+    let periods_delta = current.periods.saturating_sub(prev.periods);
+    let throttled_delta = current
+        .throttled_periods
+        .saturating_sub(prev.throttled_periods);
+
+    let ratio = if periods_delta > 0 {
+        (throttled_delta as f64 / periods_delta as f64).min(1.0)
+    } else {
+        0.0
+    };
+
+    let elapsed = prev_time.elapsed().as_secs_f64();
+    let throttled_secs = current.throttled_time.saturating_sub(prev.throttled_time) as f64 / 1e9;
+    let secs_per_sec = if elapsed > 0.0 {
+        throttled_secs / elapsed
+    } else {
+        0.0
+    };
+
+    (ratio, secs_per_sec)
+}
+
 /// Calculates memory usage percentage from container stats
+/// Page cache is excluded from usage, matching `docker stats`
 pub fn calculate_memory_percentage(stats: &ContainerStatsResponse) -> f64 {
     let memory_stats = match &stats.memory_stats {
         Some(ms) => ms,
         None => return 0.0,
     };
 
-    let memory_usage = memory_stats.usage.unwrap_or(0) as f64;
+    let memory_usage = calculate_memory_usage(memory_stats) as f64;
     let memory_limit = memory_stats.limit.unwrap_or(1) as f64;
 
     if memory_limit > 0.0 {
@@ -150,6 +291,107 @@ pub fn calculate_memory_percentage(stats: &ContainerStatsResponse) -> f64 {
     }
 }
 
+/// Extracts memory usage and limit in bytes from container stats
+fn extract_memory_bytes(stats: &ContainerStatsResponse) -> (Option<u64>, Option<u64>) {
+    let memory_stats = match &stats.memory_stats {
+        Some(ms) => ms,
+        None => return (None, None),
+    };
+
+    let limit = memory_stats.limit.filter(|&limit| limit > 0);
+
+    (
+        memory_stats
+            .usage
+            .map(|_| calculate_memory_usage(memory_stats)),
+        limit,
+    )
+}
+
+/// Calculates memory usage in bytes without reclaimable page cache, like the Docker CLI
+///
+/// cgroup v1 reports `total_inactive_file` (or only `cache` on older kernels),
+/// cgroup v2 reports `inactive_file`. The first of these below usage is subtracted, and
+/// usage is returned unchanged when none is.
+fn calculate_memory_usage(memory_stats: &ContainerMemoryStats) -> u64 {
+    let usage = memory_stats.usage.unwrap_or(0);
+
+    let Some(stats) = &memory_stats.stats else {
+        return usage;
+    };
+
+    // Like the Docker CLI, skip values that aren't below usage and try the next field
+    let cache = ["total_inactive_file", "inactive_file", "cache"]
+        .iter()
+        .filter_map(|field| stats.get(*field).copied())
+        .find(|&cache| cache < usage);
+
+    usage - cache.unwrap_or(0)
+}
+
+/// Maps the cgroup memory.stat fields onto a memory breakdown
+///
+/// cgroup v2 reports `anon`, `file`, `shmem` and `kernel` (or only `kernel_stack`,
+/// `slab` and `pagetables` on kernels before 5.18). cgroup v1 reports hierarchical
+/// `total_*` fields, with swap but without kernel memory.
+fn extract_memory_breakdown(stats: &ContainerStatsResponse) -> MemoryBreakdown {
+    let Some(fields) = stats
+        .memory_stats
+        .as_ref()
+        .and_then(|memory_stats| memory_stats.stats.as_ref())
+    else {
+        return MemoryBreakdown::default();
+    };
+
+    let field = |name: &str| fields.get(name).copied();
+
+    // cgroup v1 hierarchical stats are prefixed with "total_"
+    if fields.contains_key("total_rss") {
+        return MemoryBreakdown {
+            anon: field("total_rss"),
+            file: field("total_cache"),
+            kernel: None,
+            shmem: field("total_shmem"),
+            swap: field("total_swap"),
+            pgfault: field("total_pgfault"),
+            pgmajfault: field("total_pgmajfault"),
+        };
+    }
+
+    let kernel = field("kernel").or_else(|| {
+        let parts = ["kernel_stack", "slab", "pagetables"].map(field);
+        parts
+            .iter()
+            .any(Option::is_some)
+            .then(|| parts.iter().flatten().sum())
+    });
+
+    MemoryBreakdown {
+        anon: field("anon"),
+        file: field("file"),
+        kernel,
+        shmem: field("shmem"),
+        swap: None,
+        pgfault: field("pgfault"),
+        pgmajfault: field("pgmajfault"),
+    }
+}
+
+/// Extracts the number of PIDs and the PIDs limit from container stats
+/// A limit of 0 (or the cgroup v2 "max" reported as u64::MAX) means there is no limit
+fn extract_pids(stats: &ContainerStatsResponse) -> (Option<u64>, Option<u64>) {
+    let pids_stats = match &stats.pids_stats {
+        Some(ps) => ps,
+        None => return (None, None),
+    };
+
+    let limit = pids_stats
+        .limit
+        .filter(|&limit| limit != 0 && limit != u64::MAX);
+
+    (pids_stats.current, limit)
+}
+
 /// Extracts total network bytes (tx, rx) from container stats
 fn extract_network_bytes(stats: &ContainerStatsResponse) -> (Option<u64>, Option<u64>) {
     let networks = match &stats.networks {
@@ -202,10 +444,121 @@ fn calculate_network_rates(
     (tx_rate, rx_rate)
 }
 
+/// Calculates per-interface network rates and error counters, sorted by interface name
+///
+/// Rates are zero for the first sample and for interfaces that just appeared.
+/// Errors and drops are cumulative counts since the interface was created.
+fn calculate_interface_stats(
+    stats: &ContainerStatsResponse,
+    prev: Option<&HashMap<String, ContainerNetworkStats>>,
+    prev_time: Option<Instant>,
+) -> Vec<InterfaceStats> {
+    let Some(networks) = &stats.networks else {
+        return Vec::new();
+    };
+
+    let elapsed = prev_time.map_or(0.0, |time| time.elapsed().as_secs_f64());
+
+    let mut interfaces: Vec<InterfaceStats> = networks
+        .iter()
+        .map(|(name, current)| {
+            let previous = prev.and_then(|p| p.get(name));
+            let rate = |field: fn(&ContainerNetworkStats) -> Option<u64>| match previous {
+                Some(previous) if elapsed > 0.0 => {
+                    let delta = field(current)
+                        .unwrap_or(0)
+                        .saturating_sub(field(previous).unwrap_or(0));
+                    delta as f64 / elapsed
+                }
+                _ => 0.0,
+            };
+
+            InterfaceStats {
+                name: name.clone(),
+                rx_bytes_per_sec: rate(|n| n.rx_bytes),
+                tx_bytes_per_sec: rate(|n| n.tx_bytes),
+                rx_packets_per_sec: rate(|n| n.rx_packets),
+                tx_packets_per_sec: rate(|n| n.tx_packets),
+                rx_errors: current.rx_errors.unwrap_or(0),
+                tx_errors: current.tx_errors.unwrap_or(0),
+                rx_dropped: current.rx_dropped.unwrap_or(0),
+                tx_dropped: current.tx_dropped.unwrap_or(0),
+            }
+        })
+        .collect();
+
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+/// Extracts total block I/O bytes (read, write) from container stats
+///
+/// Both cgroup layouts report `io_service_bytes_recursive`, one entry per device and
+/// operation. cgroup v1 uses "Read"/"Write" (plus "Sync", "Async" and "Total" entries
+/// that would double count), cgroup v2 uses "read"/"write".
+fn extract_block_io_bytes(stats: &ContainerStatsResponse) -> (Option<u64>, Option<u64>) {
+    let entries = match stats
+        .blkio_stats
+        .as_ref()
+        .and_then(|blkio| blkio.io_service_bytes_recursive.as_ref())
+    {
+        Some(entries) => entries,
+        None => return (None, None),
+    };
+
+    let mut total_read = 0u64;
+    let mut total_write = 0u64;
+
+    for entry in entries {
+        let value = entry.value.unwrap_or(0);
+        match entry.op.as_deref() {
+            Some(op) if op.eq_ignore_ascii_case("read") => total_read += value,
+            Some(op) if op.eq_ignore_ascii_case("write") => total_write += value,
+            _ => {}
+        }
+    }
+
+    (Some(total_read), Some(total_write))
+}
+
+/// Calculates block I/O rates (read, write) in bytes per second
+fn calculate_block_io_rates(
+    stats: &ContainerStatsResponse,
+    prev_read: Option<u64>,
+    prev_write: Option<u64>,
+    prev_time: Option<Instant>,
+) -> (f64, f64) {
+    let (current_read, current_write) = extract_block_io_bytes(stats);
+
+    // If we don't have previous values, return 0
+    let (prev_read, prev_write, prev_time) = match (prev_read, prev_write, prev_time) {
+        (Some(read), Some(write), Some(time)) => (read, write, time),
+        _ => return (0.0, 0.0),
+    };
+
+    let (current_read, current_write) = match (current_read, current_write) {
+        (Some(read), Some(write)) => (read, write),
+        _ => return (0.0, 0.0),
+    };
+
+    let elapsed = prev_time.elapsed().as_secs_f64();
+    if elapsed <= 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let read_delta = current_read.saturating_sub(prev_read) as f64;
+    let write_delta = current_write.saturating_sub(prev_write) as f64;
+
+    (read_delta / elapsed, write_delta / elapsed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bollard::models::{ContainerCpuStats, ContainerCpuUsage, ContainerMemoryStats};
+    use bollard::models::{
+        ContainerBlkioStatEntry, ContainerBlkioStats, ContainerCpuStats, ContainerCpuUsage,
+        ContainerPidsStats, ContainerThrottlingData,
+    };
 
     fn create_cpu_stats(
         total_usage: u64,
@@ -408,4 +761,429 @@ mod tests {
         // Should handle division by zero gracefully
         assert_eq!(calculate_memory_percentage(&stats), 0.0);
     }
+
+    fn blkio_entry(major: u64, op: &str, value: u64) -> ContainerBlkioStatEntry {
+        ContainerBlkioStatEntry {
+            major: Some(major),
+            minor: Some(0),
+            op: Some(op.to_string()),
+            value: Some(value),
+        }
+    }
+
+    fn blkio_stats(entries: Vec<ContainerBlkioStatEntry>) -> ContainerStatsResponse {
+        ContainerStatsResponse {
+            blkio_stats: Some(ContainerBlkioStats {
+                io_service_bytes_recursive: Some(entries),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_extract_block_io_bytes_cgroup_v1() {
+        // v1 reports Sync/Async/Total alongside Read/Write, which must not be counted
+        let stats = blkio_stats(vec![
+            blkio_entry(8, "Read", 1000),
+            blkio_entry(8, "Write", 2000),
+            blkio_entry(8, "Sync", 3000),
+            blkio_entry(8, "Async", 0),
+            blkio_entry(8, "Total", 3000),
+            blkio_entry(253, "Read", 500),
+            blkio_entry(253, "Write", 0),
+        ]);
+
+        assert_eq!(extract_block_io_bytes(&stats), (Some(1500), Some(2000)));
+    }
+
+    #[test]
+    fn test_extract_block_io_bytes_cgroup_v2() {
+        let stats = blkio_stats(vec![
+            blkio_entry(259, "read", 4096),
+            blkio_entry(259, "write", 8192),
+            blkio_entry(8, "read", 1024),
+            blkio_entry(8, "write", 0),
+        ]);
+
+        assert_eq!(extract_block_io_bytes(&stats), (Some(5120), Some(8192)));
+    }
+
+    #[test]
+    fn test_extract_block_io_bytes_missing_blkio_stats() {
+        let stats = ContainerStatsResponse::default();
+        assert_eq!(extract_block_io_bytes(&stats), (None, None));
+
+        // cgroup v2 hosts without I/O accounting report null entries
+        let stats = ContainerStatsResponse {
+            blkio_stats: Some(ContainerBlkioStats::default()),
+            ..Default::default()
+        };
+        assert_eq!(extract_block_io_bytes(&stats), (None, None));
+    }
+
+    #[test]
+    fn test_calculate_block_io_rates_without_previous_values() {
+        let stats = blkio_stats(vec![blkio_entry(8, "read", 4096)]);
+        assert_eq!(
+            calculate_block_io_rates(&stats, None, None, None),
+            (0.0, 0.0)
+        );
+    }
+
+    fn pids_stats(current: Option<u64>, limit: Option<u64>) -> ContainerStatsResponse {
+        ContainerStatsResponse {
+            pids_stats: Some(ContainerPidsStats { current, limit }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_extract_pids_with_limit() {
+        let stats = pids_stats(Some(12), Some(100));
+        assert_eq!(extract_pids(&stats), (Some(12), Some(100)));
+    }
+
+    #[test]
+    fn test_extract_pids_without_limit() {
+        assert_eq!(extract_pids(&pids_stats(Some(3), Some(0))), (Some(3), None));
+        assert_eq!(
+            extract_pids(&pids_stats(Some(3), Some(u64::MAX))),
+            (Some(3), None)
+        );
+        assert_eq!(extract_pids(&pids_stats(Some(3), None)), (Some(3), None));
+    }
+
+    #[test]
+    fn test_extract_pids_missing_pids_stats() {
+        let stats = ContainerStatsResponse::default();
+        assert_eq!(extract_pids(&stats), (None, None));
+    }
+
+    #[test]
+    fn test_extract_memory_bytes() {
+        let stats = ContainerStatsResponse {
+            memory_stats: Some(ContainerMemoryStats {
+                usage: Some(512 * 1024 * 1024),
+                limit: Some(2 * 1024 * 1024 * 1024),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            extract_memory_bytes(&stats),
+            (Some(512 * 1024 * 1024), Some(2 * 1024 * 1024 * 1024))
+        );
+    }
+
+    #[test]
+    fn test_extract_memory_bytes_zero_limit() {
+        let stats = ContainerStatsResponse {
+            memory_stats: Some(ContainerMemoryStats {
+                usage: Some(1024),
+                limit: Some(0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(extract_memory_bytes(&stats), (Some(1024), None));
+        assert_eq!(
+            extract_memory_bytes(&ContainerStatsResponse::default()),
+            (None, None)
+        );
+    }
+
+    fn memory_stats_with(usage: u64, limit: u64, fields: &[(&str, u64)]) -> ContainerStatsResponse {
+        ContainerStatsResponse {
+            memory_stats: Some(ContainerMemoryStats {
+                usage: Some(usage),
+                limit: Some(limit),
+                stats: Some(
+                    fields
+                        .iter()
+                        .map(|(name, value)| (name.to_string(), *value))
+                        .collect(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_calculate_memory_percentage_cgroup_v2_excludes_inactive_file() {
+        let stats = memory_stats_with(
+            600_000_000,
+            1_000_000_000,
+            &[("inactive_file", 100_000_000), ("active_file", 50_000_000)],
+        );
+
+        assert_eq!(calculate_memory_percentage(&stats), 50.0);
+        assert_eq!(extract_memory_bytes(&stats).0, Some(500_000_000));
+    }
+
+    #[test]
+    fn test_calculate_memory_percentage_cgroup_v1_excludes_total_inactive_file() {
+        // total_inactive_file takes precedence over cache on cgroup v1
+        let stats = memory_stats_with(
+            600_000_000,
+            1_000_000_000,
+            &[
+                ("total_inactive_file", 200_000_000),
+                ("cache", 300_000_000),
+                ("inactive_file", 150_000_000),
+            ],
+        );
+
+        assert_eq!(calculate_memory_percentage(&stats), 40.0);
+    }
+
+    #[test]
+    fn test_calculate_memory_percentage_cgroup_v1_falls_back_to_cache() {
+        let stats = memory_stats_with(600_000_000, 1_000_000_000, &[("cache", 300_000_000)]);
+
+        assert_eq!(calculate_memory_percentage(&stats), 30.0);
+    }
+
+    #[test]
+    fn test_calculate_memory_usage_ignores_cache_larger_than_usage() {
+        let stats = memory_stats_with(
+            100_000_000,
+            1_000_000_000,
+            &[("inactive_file", 200_000_000)],
+        );
+
+        assert_eq!(calculate_memory_percentage(&stats), 10.0);
+    }
+
+    #[test]
+    fn test_calculate_memory_usage_tries_next_field_when_larger_than_usage() {
+        let stats = memory_stats_with(
+            600_000_000,
+            1_000_000_000,
+            &[
+                ("total_inactive_file", 700_000_000),
+                ("inactive_file", 200_000_000),
+            ],
+        );
+
+        assert_eq!(calculate_memory_percentage(&stats), 40.0);
+    }
+
+    #[test]
+    fn test_extract_memory_breakdown_cgroup_v2() {
+        let stats = memory_stats_with(
+            600_000_000,
+            1_000_000_000,
+            &[
+                ("anon", 400_000_000),
+                ("file", 150_000_000),
+                ("kernel_stack", 1_000_000),
+                ("slab", 8_000_000),
+                ("pagetables", 1_000_000),
+                ("shmem", 4_096),
+                ("pgfault", 1_200),
+                ("pgmajfault", 3),
+            ],
+        );
+
+        assert_eq!(
+            extract_memory_breakdown(&stats),
+            MemoryBreakdown {
+                anon: Some(400_000_000),
+                file: Some(150_000_000),
+                kernel: Some(10_000_000),
+                shmem: Some(4_096),
+                swap: None,
+                pgfault: Some(1_200),
+                pgmajfault: Some(3),
+            }
+        );
+    }
+
+    #[test]
+    fn test_extract_memory_breakdown_cgroup_v2_kernel_field() {
+        let stats = memory_stats_with(
+            600_000_000,
+            1_000_000_000,
+            &[("kernel", 12_000_000), ("slab", 8_000_000)],
+        );
+
+        assert_eq!(extract_memory_breakdown(&stats).kernel, Some(12_000_000));
+    }
+
+    #[test]
+    fn test_extract_memory_breakdown_cgroup_v1() {
+        let stats = memory_stats_with(
+            600_000_000,
+            1_000_000_000,
+            &[
+                ("rss", 1),
+                ("total_rss", 300_000_000),
+                ("total_cache", 200_000_000),
+                ("total_shmem", 0),
+                ("total_swap", 50_000_000),
+                ("total_pgfault", 900),
+                ("total_pgmajfault", 7),
+            ],
+        );
+
+        assert_eq!(
+            extract_memory_breakdown(&stats),
+            MemoryBreakdown {
+                anon: Some(300_000_000),
+                file: Some(200_000_000),
+                kernel: None,
+                shmem: Some(0),
+                swap: Some(50_000_000),
+                pgfault: Some(900),
+                pgmajfault: Some(7),
+            }
+        );
+    }
+
+    #[test]
+    fn test_extract_memory_breakdown_missing_stats() {
+        let stats = ContainerStatsResponse::default();
+        assert_eq!(extract_memory_breakdown(&stats), MemoryBreakdown::default());
+    }
+
+    fn throttling_stats(
+        periods: u64,
+        throttled_periods: u64,
+        throttled_time: u64,
+    ) -> ContainerStatsResponse {
+        ContainerStatsResponse {
+            cpu_stats: Some(ContainerCpuStats {
+                throttling_data: Some(ContainerThrottlingData {
+                    periods: Some(periods),
+                    throttled_periods: Some(throttled_periods),
+                    throttled_time: Some(throttled_time),
+                }),
+                ..create_cpu_stats(0, 0, 1)
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_calculate_throttling_ratio() {
+        let prev = extract_throttling(&throttling_stats(100, 10, 1_000_000_000));
+        let stats = throttling_stats(200, 35, 1_500_000_000);
+
+        let (ratio, secs_per_sec) = calculate_throttling(&stats, prev, Some(Instant::now()));
+
+        // 25 of 100 new periods were throttled
+        assert_eq!(ratio, 0.25);
+        assert!(secs_per_sec > 0.0);
+    }
+
+    #[test]
+    fn test_calculate_throttling_without_quota() {
+        // Containers without a CPU quota report zero periods
+        let prev = extract_throttling(&throttling_stats(0, 0, 0));
+        let stats = throttling_stats(0, 0, 0);
+
+        assert_eq!(
+            calculate_throttling(&stats, prev, Some(Instant::now())),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_calculate_throttling_without_previous_values() {
+        let stats = throttling_stats(200, 35, 1_500_000_000);
+        assert_eq!(calculate_throttling(&stats, None, None), (0.0, 0.0));
+    }
+
+    fn network_stats(interfaces: &[(&str, u64, u64, u64)]) -> ContainerStatsResponse {
+        ContainerStatsResponse {
+            networks: Some(
+                interfaces
+                    .iter()
+                    .map(|(name, rx_bytes, rx_packets, rx_dropped)| {
+                        (
+                            name.to_string(),
+                            ContainerNetworkStats {
+                                rx_bytes: Some(*rx_bytes),
+                                rx_packets: Some(*rx_packets),
+                                rx_dropped: Some(*rx_dropped),
+                                ..Default::default()
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_calculate_interface_stats_per_interface() {
+        let prev = network_stats(&[("eth0", 1000, 10, 0), ("eth1", 500, 5, 2)]);
+        let stats = network_stats(&[("eth1", 500, 5, 3), ("eth0", 3000, 30, 0)]);
+
+        let interfaces = calculate_interface_stats(
+            &stats,
+            prev.networks.as_ref(),
+            Some(Instant::now() - std::time::Duration::from_secs(1)),
+        );
+
+        assert_eq!(interfaces.len(), 2);
+        assert_eq!(interfaces[0].name, "eth0");
+        assert!(interfaces[0].rx_bytes_per_sec > 0.0);
+        assert!(interfaces[0].rx_packets_per_sec > 0.0);
+        assert_eq!(interfaces[1].name, "eth1");
+        assert_eq!(interfaces[1].rx_bytes_per_sec, 0.0);
+        assert_eq!(interfaces[1].rx_dropped, 3);
+    }
+
+    #[test]
+    fn test_calculate_interface_stats_first_sample() {
+        let stats = network_stats(&[("eth0", 3000, 30, 1)]);
+        let interfaces = calculate_interface_stats(&stats, None, None);
+
+        assert_eq!(interfaces[0].rx_bytes_per_sec, 0.0);
+        assert_eq!(interfaces[0].rx_dropped, 1);
+    }
+
+    #[test]
+    fn test_cpu_limit_cores_from_nano_cpus() {
+        let config = HostConfig {
+            nano_cpus: Some(1_500_000_000),
+            ..Default::default()
+        };
+
+        assert_eq!(cpu_limit_cores(&config), Some(1.5));
+    }
+
+    #[test]
+    fn test_cpu_limit_cores_from_quota() {
+        let config = HostConfig {
+            cpu_quota: Some(50_000),
+            cpu_period: Some(100_000),
+            ..Default::default()
+        };
+        assert_eq!(cpu_limit_cores(&config), Some(0.5));
+
+        // Default period when only the quota is set
+        let config = HostConfig {
+            cpu_quota: Some(200_000),
+            ..Default::default()
+        };
+        assert_eq!(cpu_limit_cores(&config), Some(2.0));
+    }
+
+    #[test]
+    fn test_cpu_limit_cores_unlimited() {
+        let config = HostConfig {
+            nano_cpus: Some(0),
+            cpu_quota: Some(-1),
+            ..Default::default()
+        };
+
+        assert_eq!(cpu_limit_cores(&config), None);
+    }
 }
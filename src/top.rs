@@ -0,0 +1,425 @@
+use bollard::models::ContainerTopResponse;
+use bollard::query_parameters::TopOptions;
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::docker::DockerHost;
+use crate::types::{AppEvent, ContainerKey, EventSender};
+
+/// How often the process list is refreshed
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Window over which per-process CPU usage is measured
+/// `ps` reports CPU time in whole seconds, so a longer window gives finer readings
+const CPU_WINDOW: Duration = Duration::from_secs(10);
+
+/// A single process running inside a container
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub user: String,
+    /// Recent CPU usage in percent, from the change in CPU time between polls
+    /// (the lifetime average reported by `ps` until a second poll is available)
+    pub cpu: f64,
+    /// Total CPU time used in seconds (None when `ps` doesn't report it)
+    pub cpu_time_secs: Option<f64>,
+    /// Memory usage in percent, as reported by `ps`
+    pub memory: f64,
+    pub command: String,
+}
+
+/// Column the process list is sorted by
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProcessSort {
+    Pid,
+    User,
+    Cpu,
+    Memory,
+    Command,
+}
+
+impl ProcessSort {
+    /// Whether this column sorts largest-first when first selected
+    pub fn default_descending(self) -> bool {
+        matches!(self, ProcessSort::Cpu | ProcessSort::Memory)
+    }
+}
+
+/// Process list for the container shown in the process view
+#[derive(Clone, Debug)]
+pub struct ProcessList {
+    pub key: ContainerKey,
+    pub processes: Vec<ProcessInfo>,
+    /// Error from the most recent refresh, if it failed
+    pub error: Option<String>,
+    pub sort: ProcessSort,
+    pub descending: bool,
+}
+
+impl ProcessList {
+    pub fn new(key: ContainerKey) -> Self {
+        Self {
+            key,
+            processes: Vec::new(),
+            error: None,
+            sort: ProcessSort::Cpu,
+            descending: true,
+        }
+    }
+
+    /// Replaces the process list with a fresh poll result, keeping the current sort order
+    pub fn update(&mut self, result: Result<Vec<ProcessInfo>, String>) {
+        match result {
+            Ok(processes) => {
+                self.processes = processes;
+                self.error = None;
+                sort_processes(&mut self.processes, self.sort, self.descending);
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    /// Sorts by a column; selecting the current column again reverses the direction
+    pub fn set_sort(&mut self, sort: ProcessSort) {
+        if self.sort == sort {
+            self.descending = !self.descending;
+        } else {
+            self.sort = sort;
+            self.descending = sort.default_descending();
+        }
+        sort_processes(&mut self.processes, self.sort, self.descending);
+    }
+}
+
+/// Tracks each process's CPU time across polls to compute its current CPU usage
+#[derive(Debug, Default)]
+pub struct CpuTracker {
+    /// CPU time samples per PID, oldest first, spanning at most CPU_WINDOW
+    samples: HashMap<u32, VecDeque<(Instant, f64)>>,
+}
+
+impl CpuTracker {
+    /// Records the CPU time of each process and replaces its CPU usage with the
+    /// usage over the last CPU_WINDOW, once there are two samples to compare
+    pub fn update(&mut self, processes: &mut [ProcessInfo], now: Instant) {
+        // Forget processes that have exited so reused PIDs start fresh
+        self.samples
+            .retain(|pid, _| processes.iter().any(|p| p.pid == *pid));
+
+        for process in processes {
+            let Some(cpu_time) = process.cpu_time_secs else {
+                continue;
+            };
+
+            let samples = self.samples.entry(process.pid).or_default();
+            // CPU time going backwards means the PID now belongs to another process
+            if samples
+                .back()
+                .is_some_and(|&(_, previous)| cpu_time < previous)
+            {
+                samples.clear();
+            }
+            samples.push_back((now, cpu_time));
+
+            // Keep the newest sample that is at least CPU_WINDOW old as the baseline
+            while samples.len() > 2 && now.duration_since(samples[1].0) >= CPU_WINDOW {
+                samples.pop_front();
+            }
+
+            let (start, start_time) = samples[0];
+            let elapsed = now.duration_since(start).as_secs_f64();
+            if elapsed > 0.0 {
+                process.cpu = (cpu_time - start_time) / elapsed * 100.0;
+            }
+        }
+    }
+}
+
+/// Parses a `ps` CPU time such as "0:03", "01:02:03" or "2-01:00:00" into seconds
+fn parse_cpu_time(time: &str) -> Option<f64> {
+    let (days, time) = match time.split_once('-') {
+        Some((days, time)) => (days.parse::<u64>().ok()?, time),
+        None => (0, time),
+    };
+
+    let secs = time.split(':').try_fold(0u64, |total, part| {
+        Some(total * 60 + part.parse::<u64>().ok()?)
+    })?;
+
+    Some((days * 86_400 + secs) as f64)
+}
+
+/// Polls the `top` API for a container and sends the process list on every refresh
+///
+/// # Arguments
+/// * `host` - Docker host instance with identifier
+/// * `container_id` - Container ID (truncated IDs are accepted by the Docker API)
+/// * `tx` - Event sender channel
+pub async fn poll_container_processes(host: DockerHost, container_id: String, tx: EventSender) {
+    let key = ContainerKey::new(host.host_id.clone(), container_id.clone());
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    let mut cpu_tracker = CpuTracker::default();
+
+    loop {
+        interval.tick().await;
+
+        // `ps` runs on the Docker host, so the BSD-style columns are always available
+        let options = Some(TopOptions {
+            ps_args: "aux".to_string(),
+        });
+
+        let result = host
+            .docker
+            .top_processes(&container_id, options)
+            .await
+            .map(|response| {
+                let mut processes = parse_top_response(&response);
+                cpu_tracker.update(&mut processes, Instant::now());
+                processes
+            })
+            .map_err(|e| e.to_string());
+
+        if tx
+            .send(AppEvent::ProcessList(key.clone(), result))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Converts a `top` response into process entries by looking up columns by title
+/// Columns that are missing from the response are left empty or zero
+pub fn parse_top_response(response: &ContainerTopResponse) -> Vec<ProcessInfo> {
+    let titles = response.titles.as_deref().unwrap_or_default();
+    let column = |names: &[&str]| titles.iter().position(|t| names.contains(&t.as_str()));
+
+    let pid_col = column(&["PID"]);
+    let user_col = column(&["USER", "UID"]);
+    let cpu_col = column(&["%CPU", "C"]);
+    let memory_col = column(&["%MEM"]);
+    let time_col = column(&["TIME"]);
+    let command_col = column(&["COMMAND", "CMD"]);
+
+    let field = |row: &[String], col: Option<usize>| -> String {
+        col.and_then(|idx| row.get(idx))
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    response
+        .processes
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|row| ProcessInfo {
+            pid: field(row, pid_col).parse().unwrap_or(0),
+            user: field(row, user_col),
+            cpu: field(row, cpu_col).parse().unwrap_or(0.0),
+            memory: field(row, memory_col).parse().unwrap_or(0.0),
+            cpu_time_secs: parse_cpu_time(&field(row, time_col)),
+            command: field(row, command_col),
+        })
+        .collect()
+}
+
+/// Sorts processes in place by the given column
+pub fn sort_processes(processes: &mut [ProcessInfo], sort: ProcessSort, descending: bool) {
+    processes.sort_by(|a, b| {
+        let ordering = match sort {
+            ProcessSort::Pid => a.pid.cmp(&b.pid),
+            ProcessSort::User => a.user.cmp(&b.user),
+            ProcessSort::Cpu => a.cpu.partial_cmp(&b.cpu).unwrap_or(Ordering::Equal),
+            ProcessSort::Memory => a.memory.partial_cmp(&b.memory).unwrap_or(Ordering::Equal),
+            ProcessSort::Command => a.command.cmp(&b.command),
+        };
+
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn top_response() -> ContainerTopResponse {
+        ContainerTopResponse {
+            titles: Some(strings(&[
+                "USER", "PID", "%CPU", "%MEM", "VSZ", "RSS", "TTY", "STAT", "START", "TIME",
+                "COMMAND",
+            ])),
+            processes: Some(vec![
+                strings(&[
+                    "root",
+                    "1",
+                    "0.1",
+                    "0.5",
+                    "1000",
+                    "500",
+                    "?",
+                    "Ss",
+                    "10:00",
+                    "0:00",
+                    "nginx: master process",
+                ]),
+                strings(&[
+                    "101",
+                    "29",
+                    "12.5",
+                    "1.2",
+                    "2000",
+                    "900",
+                    "?",
+                    "S",
+                    "10:00",
+                    "0:03",
+                    "nginx: worker process",
+                ]),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_parse_top_response_aux_columns() {
+        let processes = parse_top_response(&top_response());
+
+        assert_eq!(processes.len(), 2);
+        assert_eq!(
+            processes[1],
+            ProcessInfo {
+                pid: 29,
+                user: "101".to_string(),
+                cpu: 12.5,
+                memory: 1.2,
+                cpu_time_secs: Some(3.0),
+                command: "nginx: worker process".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_cpu_time() {
+        assert_eq!(parse_cpu_time("0:03"), Some(3.0));
+        assert_eq!(parse_cpu_time("01:02:03"), Some(3723.0));
+        assert_eq!(parse_cpu_time("2-01:00:00"), Some(176_400.0));
+        assert_eq!(parse_cpu_time(""), None);
+    }
+
+    #[test]
+    fn test_cpu_tracker_uses_recent_cpu_time() {
+        let mut tracker = CpuTracker::default();
+        let start = Instant::now();
+        let poll = |cpu_time: f64| {
+            vec![ProcessInfo {
+                pid: 7,
+                user: "root".to_string(),
+                cpu: 0.1, // Lifetime average from `ps`
+                memory: 0.0,
+                cpu_time_secs: Some(cpu_time),
+                command: "worker".to_string(),
+            }]
+        };
+
+        let mut processes = poll(600.0);
+        tracker.update(&mut processes, start);
+        assert_eq!(processes[0].cpu, 0.1);
+
+        // Spinning on one core after being idle
+        let mut processes = poll(604.0);
+        tracker.update(&mut processes, start + Duration::from_secs(4));
+        assert_eq!(processes[0].cpu, 100.0);
+
+        // The window keeps the baseline at most CPU_WINDOW old
+        for secs in [8, 12, 16] {
+            processes = poll(600.0 + secs as f64 / 2.0 + 2.0);
+            tracker.update(&mut processes, start + Duration::from_secs(secs));
+        }
+        assert_eq!(processes[0].cpu, 50.0);
+
+        // A reused PID starts over
+        let mut processes = poll(1.0);
+        tracker.update(&mut processes, start + Duration::from_secs(20));
+        assert_eq!(processes[0].cpu, 0.1);
+    }
+
+    #[test]
+    fn test_parse_top_response_default_ps_columns() {
+        // Default `ps -ef` output has no %MEM column
+        let response = ContainerTopResponse {
+            titles: Some(strings(&[
+                "UID", "PID", "PPID", "C", "STIME", "TTY", "TIME", "CMD",
+            ])),
+            processes: Some(vec![strings(&[
+                "root",
+                "7",
+                "1",
+                "3",
+                "10:00",
+                "?",
+                "00:00:01",
+                "sleep 100",
+            ])]),
+        };
+
+        let processes = parse_top_response(&response);
+        assert_eq!(processes[0].pid, 7);
+        assert_eq!(processes[0].user, "root");
+        assert_eq!(processes[0].cpu, 3.0);
+        assert_eq!(processes[0].memory, 0.0);
+        assert_eq!(processes[0].command, "sleep 100");
+    }
+
+    #[test]
+    fn test_parse_top_response_empty() {
+        assert!(parse_top_response(&ContainerTopResponse::default()).is_empty());
+    }
+
+    #[test]
+    fn test_process_list_set_sort_toggles_direction() {
+        let mut list = ProcessList::new(ContainerKey::new("local".into(), "abc".into()));
+        list.update(Ok(parse_top_response(&top_response())));
+        assert_eq!(list.processes[0].pid, 29); // CPU descending by default
+
+        list.set_sort(ProcessSort::Pid);
+        assert!(!list.descending);
+        assert_eq!(list.processes[0].pid, 1);
+
+        list.set_sort(ProcessSort::Pid);
+        assert!(list.descending);
+        assert_eq!(list.processes[0].pid, 29);
+    }
+
+    #[test]
+    fn test_process_list_keeps_processes_on_error() {
+        let mut list = ProcessList::new(ContainerKey::new("local".into(), "abc".into()));
+        list.update(Ok(parse_top_response(&top_response())));
+        list.update(Err("container not running".to_string()));
+
+        assert_eq!(list.processes.len(), 2);
+        assert_eq!(list.error.as_deref(), Some("container not running"));
+    }
+
+    #[test]
+    fn test_sort_processes() {
+        let mut processes = parse_top_response(&top_response());
+
+        sort_processes(&mut processes, ProcessSort::Cpu, true);
+        assert_eq!(processes[0].pid, 29);
+
+        sort_processes(&mut processes, ProcessSort::Pid, false);
+        assert_eq!(processes[0].pid, 1);
+
+        sort_processes(&mut processes, ProcessSort::Command, true);
+        assert_eq!(processes[0].command, "nginx: worker process");
+    }
+}
@@ -1,6 +1,16 @@
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
 use tokio::sync::mpsc;
 
+use crate::compose::ComposeProgress;
+use crate::diff::ContainerChanges;
+use crate::exec::{ExecLine, ExecStatus};
+use crate::files::PathContents;
+use crate::grouping::{COMPOSE_PROJECT_LABEL, COMPOSE_SERVICE_LABEL};
+use crate::health::{Health, HealthCheck};
+use crate::host::HostInfo;
 use crate::logs::LogEntry;
+use crate::top::ProcessInfo;
 
 /// Host identifier for tracking which Docker host a container belongs to
 pub type HostId = String;
@@ -10,20 +20,171 @@ pub type HostId = String;
 pub struct Container {
     pub id: String,
     pub name: String,
+    /// Plain container state, e.g. "running" or "paused"
     pub status: String,
     pub stats: ContainerStats,
     pub host_id: HostId,
+    pub labels: HashMap<String, String>,
+    /// Health check status, kept apart from the state and updated by health events
+    pub health: Health,
+    pub lifecycle: Lifecycle,
+}
+
+/// Lifecycle events seen for a container since it was last started
+#[derive(Clone, Debug, Default)]
+pub struct Lifecycle {
+    /// When the container was last restarted
+    pub restarted_at: Option<DateTime<Local>>,
+    /// Last signal sent to the container, e.g. "15"
+    pub last_signal: Option<String>,
+    /// Whether a process in the container was killed for running out of memory
+    pub out_of_memory: bool,
+}
+
+impl Container {
+    /// Docker Compose project the container belongs to, if any
+    pub fn compose_project(&self) -> Option<&str> {
+        self.labels.get(COMPOSE_PROJECT_LABEL).map(String::as_str)
+    }
+
+    /// Docker Compose service the container runs, if any
+    pub fn compose_service(&self) -> Option<&str> {
+        self.labels.get(COMPOSE_SERVICE_LABEL).map(String::as_str)
+    }
+
+    /// Whether the container's health check reports it as unhealthy
+    pub fn is_unhealthy(&self) -> bool {
+        self.health == Health::Unhealthy
+    }
+
+    /// A running container named after its id, without stats, labels or health check
+    #[cfg(test)]
+    pub fn for_test(host_id: &str, id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: id.to_string(),
+            status: "running".to_string(),
+            stats: ContainerStats::default(),
+            host_id: host_id.to_string(),
+            labels: HashMap::new(),
+            health: Health::None,
+            lifecycle: Lifecycle::default(),
+        }
+    }
 }
 
 /// Container runtime statistics (updated frequently)
 #[derive(Clone, Debug, Default)]
 pub struct ContainerStats {
+    /// CPU usage in percent of one core (100% per fully used core)
     pub cpu: f64,
+    /// CPU cores available to the container - its CPU limit, or all online CPUs
+    pub cpu_limit_cores: f64,
+    /// Share of CFS periods in which the container was throttled (0.0 - 1.0)
+    pub cpu_throttled_ratio: f64,
+    /// Seconds spent throttled per second
+    pub cpu_throttled_secs_per_sec: f64,
     pub memory: f64,
     /// Network transmit rate in bytes per second
     pub network_tx_bytes_per_sec: f64,
     /// Network receive rate in bytes per second
     pub network_rx_bytes_per_sec: f64,
+    /// Per-interface network rates and error counters
+    pub network_interfaces: Vec<InterfaceStats>,
+    /// Block device read rate in bytes per second
+    pub block_read_bytes_per_sec: f64,
+    /// Block device write rate in bytes per second
+    pub block_write_bytes_per_sec: f64,
+    /// Memory used in bytes
+    pub memory_used_bytes: Option<u64>,
+    /// Memory limit in bytes (None when the container has no limit)
+    pub memory_limit_bytes: Option<u64>,
+    /// Number of processes and threads in the container
+    pub pids_current: Option<u64>,
+    /// PIDs limit of the container (None when unlimited)
+    pub pids_limit: Option<u64>,
+    /// Breakdown of memory usage by type from the cgroup's memory.stat
+    pub memory_breakdown: MemoryBreakdown,
+}
+
+/// Network statistics of a single container interface
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InterfaceStats {
+    pub name: String,
+    pub rx_bytes_per_sec: f64,
+    pub tx_bytes_per_sec: f64,
+    pub rx_packets_per_sec: f64,
+    pub tx_packets_per_sec: f64,
+    /// Total receive errors since the interface was created
+    pub rx_errors: u64,
+    /// Total transmit errors since the interface was created
+    pub tx_errors: u64,
+    /// Total received packets dropped since the interface was created
+    pub rx_dropped: u64,
+    /// Total transmitted packets dropped since the interface was created
+    pub tx_dropped: u64,
+}
+
+/// Memory usage by type, mapped from cgroup v1 or v2 memory.stat fields
+/// Fields the kernel doesn't report for the cgroup version in use are None
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryBreakdown {
+    /// Anonymous memory (RSS) in bytes
+    pub anon: Option<u64>,
+    /// Page cache in bytes
+    pub file: Option<u64>,
+    /// Kernel memory (stacks, slab, page tables) in bytes
+    pub kernel: Option<u64>,
+    /// Shared memory (tmpfs, shm) in bytes
+    pub shmem: Option<u64>,
+    /// Swap used in bytes
+    pub swap: Option<u64>,
+    /// Total page faults
+    pub pgfault: Option<u64>,
+    /// Major page faults (required disk I/O)
+    pub pgmajfault: Option<u64>,
+}
+
+/// How CPU usage is expressed in the container list
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CpuMode {
+    /// Percent of one core, so a container using 4 cores shows 400%
+    #[default]
+    PerCore,
+    /// Percent of the cores available to the container (its limit or the host)
+    OfLimit,
+}
+
+impl CpuMode {
+    pub fn toggle(self) -> Self {
+        match self {
+            CpuMode::PerCore => CpuMode::OfLimit,
+            CpuMode::OfLimit => CpuMode::PerCore,
+        }
+    }
+}
+
+/// How containers are grouped in the container list
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum GroupMode {
+    /// One row per container
+    #[default]
+    None,
+    /// Collapsible groups per Docker Compose project
+    Compose,
+    /// Collapsible sections per host
+    Host,
+}
+
+impl GroupMode {
+    /// Cycles between no grouping, Compose projects and host sections
+    pub fn next(self) -> Self {
+        match self {
+            GroupMode::None => GroupMode::Compose,
+            GroupMode::Compose => GroupMode::Host,
+            GroupMode::Host => GroupMode::None,
+        }
+    }
 }
 
 /// Unique key for identifying containers across multiple hosts
@@ -51,6 +212,20 @@ pub enum AppEvent {
     ContainerDestroyed(ContainerKey),
     /// Stats update for an existing container on a specific host
     ContainerStat(ContainerKey, ContainerStats),
+    /// Health check status of a container changed
+    ContainerHealth(ContainerKey, Health),
+    /// A container was paused
+    ContainerPaused(ContainerKey),
+    /// A paused container was resumed
+    ContainerUnpaused(ContainerKey),
+    /// A container finished restarting
+    ContainerRestarted(ContainerKey),
+    /// A process in a container was killed for running out of memory
+    ContainerOutOfMemory(ContainerKey),
+    /// A signal was sent to a container
+    ContainerKilled(ContainerKey, String),
+    /// A container was renamed
+    ContainerRenamed(ContainerKey, String),
     /// User requested to quit
     Quit,
     /// Terminal was resized
@@ -61,23 +236,70 @@ pub enum AppEvent {
     SelectNext,
     /// User pressed Enter key
     EnterPressed,
-    /// User pressed Escape to exit log view
-    ExitLogView,
+    /// User pressed Escape to close a prompt or return from a view
+    EscapePressed,
+    /// User typed a character (key bindings or prompt text, decided by the handler)
+    CharInput(char),
+    /// User pressed Backspace
+    Backspace,
     /// User scrolled up in log view
     ScrollUp,
     /// User scrolled down in log view
     ScrollDown,
     /// New log line received from streaming logs
     LogLine(ContainerKey, LogEntry),
+    /// Output line from a running exec command
+    ExecOutput(ContainerKey, ExecLine),
+    /// Exec command finished or failed to start
+    ExecFinished(ContainerKey, ExecStatus),
+    /// Refreshed process list (or the error from polling it) for a container
+    ProcessList(ContainerKey, Result<Vec<ProcessInfo>, String>),
+    /// Contents of a container path fetched for the file browser
+    PathContents(ContainerKey, String, Result<PathContents, String>),
+    /// File download finished with a success or error message
+    DownloadFinished(ContainerKey, Result<String, String>),
+    /// Filesystem changes (or the error from fetching them) for a container
+    ContainerChanges(ContainerKey, Result<ContainerChanges, String>),
+    /// Refreshed capacity and container counts (or the error from fetching them) of a host
+    HostInfo(HostId, Result<HostInfo, String>),
+    /// Recent health check results (or the error fetching them) of a container
+    HealthLog(ContainerKey, Result<Vec<HealthCheck>, String>),
+    /// Progress of the running Compose project or service action
+    ComposeProgress(ComposeProgress),
 }
 
 pub type EventSender = mpsc::Sender<AppEvent>;
 
+/// Interactive terminal session that temporarily takes over the screen
+#[derive(Clone, Debug, PartialEq)]
+pub enum InteractiveSession {
+    /// Shell started with the exec API
+    Shell(ContainerKey),
+    /// Attached to the container's main process
+    Attach(ContainerKey),
+}
+
 /// Current view state of the application
 #[derive(Clone, Debug, PartialEq)]
 pub enum ViewState {
     /// Viewing the container list
     ContainerList,
+    /// Viewing the overview of all connected hosts
+    HostList,
+    /// Viewing each service side by side across all connected hosts
+    FleetView,
     /// Viewing logs for a specific container
     LogView(ContainerKey),
+    /// Viewing output of a one-off command run in a specific container
+    ExecView(ContainerKey),
+    /// Viewing the processes running in a specific container
+    ProcessView(ContainerKey),
+    /// Browsing the filesystem of a specific container
+    FileView(ContainerKey),
+    /// Viewing live details and resource breakdowns of a specific container
+    DetailView(ContainerKey),
+    /// Viewing charts of a specific container's stats history
+    ChartView(ContainerKey),
+    /// Viewing the writable layer changes of a specific container
+    ChangesView(ContainerKey),
 }
@@ -1,14 +1,30 @@
 use ratatui::{
     Frame,
-    layout::Constraint,
+    layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
+    symbols,
+    text::{Line, Span, Text},
+    widgets::{
+        Axis, Block, Borders, Cell, Chart, Clear, Dataset, GraphType, Paragraph, Row, Table,
+        TableState, Wrap,
+    },
 };
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
 
 use crate::app_state::AppState;
-use crate::types::{Container, ContainerKey, ViewState};
+use crate::compose::{ComposeAction, ComposeOperation, ComposeTarget, StepStatus};
+use crate::diff::{ChangeKind, ContainerChanges};
+use crate::exec::{ExecStatus, ExecStream};
+use crate::files::{FileBrowser, FileKind};
+use crate::fleet::{FleetHost, FleetService};
+use crate::grouping::{GroupKey, GroupSummary, ListRow};
+use crate::health::{Health, HealthCheck};
+use crate::history::{CHART_WINDOWS, HistorySample, StatsHistory, Summary, sparkline, summarize};
+use crate::host::{HostInfo, HostUsage, host_usage};
+use crate::prompt::{Prompt, PromptAction};
+use crate::top::{ProcessList, ProcessSort};
+use crate::types::{Container, ContainerKey, ContainerStats, CpuMode, GroupMode, ViewState};
 
 /// Pre-allocated styles to avoid recreation every frame
 pub struct UiStyles {
@@ -19,6 +35,10 @@ pub struct UiStyles {
     pub border: Style,
     pub selected: Style,
     pub timestamp: Style,
+    /// Line colors of the first and second series in a chart
+    pub chart_series: [Style; 2],
+    /// Reference line marking a limit in a chart
+    pub chart_limit: Style,
 }
 
 impl Default for UiStyles {
@@ -37,108 +57,1568 @@ impl Default for UiStyles {
             timestamp: Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
+            chart_series: [
+                Style::default().fg(Color::Cyan),
+                Style::default().fg(Color::Magenta),
+            ],
+            chart_limit: Style::default().fg(Color::Red),
         }
     }
 }
 
-/// Renders the main UI - either container list or log view
+/// Renders the main UI - container list, log view or exec output, plus the command prompt
 pub fn render_ui(f: &mut Frame, state: &mut AppState, styles: &UiStyles) {
     match &state.view_state {
         ViewState::ContainerList => {
-            // Calculate unique hosts to determine if host column should be shown
-            let unique_hosts: std::collections::HashSet<_> =
-                state.containers.keys().map(|key| &key.host_id).collect();
-            let show_host_column = unique_hosts.len() > 1;
+            render_container_list(f, state, styles);
 
-            render_container_list(
+            if let Some(operation) = &state.compose_operation {
+                render_compose_operation(f, operation, styles);
+            }
+            if let Some((target, action)) = &state.compose_confirmation {
+                render_compose_confirmation(f, target, *action, styles);
+            }
+            if state.show_help {
+                render_key_help(f, styles);
+            }
+        }
+        ViewState::HostList => render_host_list(f, state, styles),
+        ViewState::FleetView => render_fleet_view(f, state, styles),
+        ViewState::LogView(container_key) => {
+            let container_key = container_key.clone();
+            render_log_view(f, &container_key, state, styles);
+        }
+        ViewState::ExecView(container_key) => {
+            let container_key = container_key.clone();
+            render_exec_view(f, &container_key, state, styles);
+        }
+        ViewState::ProcessView(container_key) => {
+            let container_name = state
+                .containers
+                .get(container_key)
+                .map(|c| c.name.as_str())
+                .unwrap_or("Unknown");
+
+            if let Some(list) = &state.current_processes {
+                render_process_view(
+                    f,
+                    list,
+                    container_name,
+                    styles,
+                    &mut state.process_table_state,
+                );
+            }
+        }
+        ViewState::FileView(container_key) => {
+            let container_name = state
+                .containers
+                .get(container_key)
+                .map(|c| c.name.as_str())
+                .unwrap_or("Unknown");
+
+            if let Some(browser) = &state.file_browser {
+                render_file_view(
+                    f,
+                    browser,
+                    container_name,
+                    styles,
+                    &mut state.file_table_state,
+                );
+            }
+        }
+        ViewState::ChartView(container_key) => {
+            let container_key = container_key.clone();
+            render_chart_view(f, &container_key, state, styles);
+        }
+        ViewState::DetailView(container_key) => {
+            let container_key = container_key.clone();
+            render_detail_view(f, &container_key, state, styles);
+        }
+        ViewState::ChangesView(container_key) => {
+            let container_name = state
+                .containers
+                .get(container_key)
+                .map(|c| c.name.as_str())
+                .unwrap_or("Unknown");
+
+            render_changes_view(
                 f,
-                &state.containers,
-                &state.sorted_container_keys,
+                state.current_changes.as_ref(),
+                container_name,
+                &container_key.host_id,
                 styles,
-                &mut state.table_state,
-                show_host_column,
+                &mut state.changes_table_state,
             );
         }
-        ViewState::LogView(container_key) => {
-            let container_key = container_key.clone();
-            render_log_view(f, &container_key, state, styles);
+    }
+
+    // The prompt is drawn on top of whichever view is active
+    if let Some(prompt) = &state.prompt {
+        let container_name = state
+            .containers
+            .get(&prompt.key)
+            .map(|c| c.name.as_str())
+            .unwrap_or("Unknown");
+        render_prompt(f, prompt, container_name, styles);
+    }
+}
+
+/// Renders the container list view
+fn render_container_list(f: &mut Frame, state: &mut AppState, styles: &UiStyles) {
+    // One summary line per listed host above the table
+    let mut hosts = state.sorted_host_ids();
+    if let Some(host_filter) = &state.host_filter {
+        hosts.retain(|&host_id| host_id == host_filter);
+    }
+
+    // The summary takes at most a third of the screen, the host overview ('h') lists the rest
+    let max_lines = (f.area().height / 3).saturating_sub(2).max(1) as usize;
+    let hidden_hosts = hosts.len().saturating_sub(max_lines);
+    let shown_hosts = &hosts[..hosts.len() - hidden_hosts];
+
+    let [summary_area, size] = Layout::vertical([
+        Constraint::Length(shown_hosts.len() as u16 + 2),
+        Constraint::Fill(1),
+    ])
+    .areas(f.area());
+
+    let usage = host_usage(&state.containers);
+    let summary_lines: Vec<Line> = shown_hosts
+        .iter()
+        .map(|&host_id| {
+            host_summary_line(
+                host_id,
+                state.host_info.get(host_id),
+                usage.get(host_id).copied().unwrap_or_default(),
+                styles,
+            )
+        })
+        .collect();
+
+    let summary_title = match &state.host_filter {
+        Some(host_id) => format!("Host {} (ESC to list all hosts)", host_id),
+        None if hidden_hosts > 0 => format!("Hosts ({} more, 'h' to list all)", hidden_hosts),
+        None => "Hosts".to_string(),
+    };
+    let summary = Paragraph::new(summary_lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(summary_title)
+            .style(styles.border),
+    );
+    f.render_widget(summary, summary_area);
+
+    // Calculate unique hosts to determine if host column should be shown
+    // Host sections already name the host above its containers
+    let unique_hosts: HashSet<_> = state.containers.keys().map(|key| &key.host_id).collect();
+    let show_host_column = unique_hosts.len() > 1 && state.group_mode != GroupMode::Host;
+
+    let columns = visible_columns(size.width, show_host_column);
+    let containers = &state.containers;
+    let stats_history = &state.stats_history;
+    let cpu_mode = state.cpu_mode;
+
+    // Use pre-sorted list instead of sorting every frame
+    let container_count = state.visible_container_keys().len();
+    let grouped = state.group_mode != GroupMode::None;
+    let rows: Vec<Row> = state
+        .list_rows()
+        .into_iter()
+        .filter_map(|row| match row {
+            ListRow::Group(group) => Some(create_group_row(&group, styles, &columns)),
+            ListRow::Container(key) => containers.get(&key).map(|c| {
+                let history = stats_history.get(&key);
+                create_container_row(c, history, styles, &columns, cpu_mode, grouped)
+            }),
+        })
+        .collect();
+
+    let header = create_header_row(styles, &columns, cpu_mode);
+    let smoothing = state
+        .smoothing_preset()
+        .map_or_else(|| "configured".to_string(), |preset| preset.to_string());
+    let table = create_table(
+        rows,
+        header,
+        container_count,
+        &smoothing,
+        styles,
+        &columns,
+        state.status_message.as_deref(),
+    );
+
+    f.render_stateful_widget(table, size, &mut state.table_state);
+}
+
+/// Renders the overview of all connected hosts, one row per host
+fn render_host_list(f: &mut Frame, state: &mut AppState, styles: &UiStyles) {
+    let size = f.area();
+    let usage = host_usage(&state.containers);
+
+    let rows: Vec<Row> = state
+        .sorted_host_ids()
+        .into_iter()
+        .map(|host_id| {
+            let host_usage = usage.get(host_id).copied().unwrap_or_default();
+            host_row(host_id, state.host_info.get(host_id), host_usage, styles)
+        })
+        .collect();
+
+    let header = Row::new(vec![
+        "Host", "Status", "Latency", "Engine", "Running", "Paused", "Stopped", "CPU", "Memory",
+        "Disk",
+    ])
+    .style(styles.header)
+    .bottom_margin(1);
+
+    let constraints = [
+        Constraint::Fill(1),    // Host
+        Constraint::Length(12), // Status
+        Constraint::Length(9),  // Latency
+        Constraint::Length(10), // Engine version
+        Constraint::Length(8),  // Running
+        Constraint::Length(8),  // Paused
+        Constraint::Length(8),  // Stopped
+        Constraint::Length(22), // CPU (1.25/8 cores 15.6%)
+        Constraint::Length(26), // Memory (3.10GiB/15.6GiB 19.9%)
+        Constraint::Length(10), // Disk
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Hosts - {} connected (↑/↓ to navigate, Enter to show a host's containers, ESC to return)",
+            state.connected_hosts.len()
+        ))
+        .style(styles.border);
+
+    let table = Table::new(rows, constraints)
+        .header(header)
+        .block(block)
+        .row_highlight_style(styles.selected);
+
+    f.render_stateful_widget(table, size, &mut state.host_table_state);
+}
+
+/// Renders each service side by side across all connected hosts
+fn render_fleet_view(f: &mut Frame, state: &mut AppState, styles: &UiStyles) {
+    let size = f.area();
+    let hosts = state.sorted_host_ids();
+    let fleet = state.fleet();
+
+    let degraded = fleet.iter().filter(|service| service.is_degraded()).count();
+    let rows: Vec<Row> = fleet
+        .iter()
+        .map(|service| fleet_row(service, styles))
+        .collect();
+
+    let mut headers = vec![Cell::from("Service")];
+    headers.extend(hosts.iter().map(|host_id| Cell::from(host_id.to_string())));
+    let header = Row::new(headers).style(styles.header).bottom_margin(1);
+
+    let mut constraints = vec![Constraint::Fill(1)];
+    constraints.extend(hosts.iter().map(|_| Constraint::Length(32)));
+
+    let matched_by = match &state.fleet_label {
+        Some(label) => format!("label {}", label),
+        None => "name".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Fleet - {} services by {}, {} missing or unhealthy on some host (ESC to return)",
+            fleet.len(),
+            matched_by,
+            degraded
+        ))
+        .style(styles.border);
+
+    let table = Table::new(rows, constraints)
+        .header(header)
+        .block(block)
+        .row_highlight_style(styles.selected);
+
+    f.render_stateful_widget(table, size, &mut state.fleet_table_state);
+}
+
+/// Creates a row of the fleet view with the service's status on each host
+fn fleet_row(service: &FleetService, styles: &UiStyles) -> Row<'static> {
+    let name = if service.is_degraded() {
+        Cell::from(format!("⚠ {}", service.name)).style(styles.medium)
+    } else {
+        Cell::from(service.name.clone())
+    };
+
+    let mut cells = vec![name];
+    cells.extend(service.hosts.iter().map(|host| fleet_cell(host, styles)));
+
+    Row::new(cells)
+}
+
+/// Status and usage of a service on one host
+fn fleet_cell(host: &FleetHost, styles: &UiStyles) -> Cell<'static> {
+    if host.is_missing() {
+        return Cell::from("missing").style(styles.high);
+    }
+
+    let usage = format!(
+        "{} running {:5.1}% {}",
+        host.running(),
+        host.cpu,
+        format_memory(host.memory_used_bytes)
+    );
+
+    if host.unhealthy {
+        Cell::from(format!("{} unhealthy", usage)).style(styles.high)
+    } else if host.paused > 0 {
+        Cell::from(format!("{} {} paused", usage, host.paused)).style(styles.medium)
+    } else {
+        Cell::from(usage).style(styles.low)
+    }
+}
+
+/// Creates a row of the host overview
+fn host_row(
+    host_id: &str,
+    info: Option<&Result<HostInfo, String>>,
+    usage: HostUsage,
+    styles: &UiStyles,
+) -> Row<'static> {
+    let host_id = host_id.to_string();
+    let info = match info {
+        Some(Ok(info)) => info,
+        Some(Err(error)) => {
+            return Row::new(vec![
+                Cell::from(host_id),
+                Cell::from("error").style(styles.high),
+                Cell::from(error.clone()).style(styles.high),
+            ]);
+        }
+        None => return Row::new(vec![Cell::from(host_id), Cell::from("connecting")]),
+    };
+
+    let percent_cell = |text: String, percent: Option<f64>| {
+        let style = percent.map_or_else(Style::default, |p| get_percentage_style(p, styles));
+        let percent = percent.map_or_else(String::new, |p| format!(" {:.1}%", p));
+        Cell::from(format!("{}{}", text, percent)).style(style)
+    };
+
+    Row::new(vec![
+        Cell::from(host_id),
+        Cell::from("connected").style(styles.low),
+        Cell::from(format!("{}ms", info.latency.as_millis())),
+        Cell::from(info.engine_version.clone()),
+        Cell::from(info.containers_running.to_string()),
+        Cell::from(info.containers_paused.to_string()),
+        Cell::from(info.containers_stopped.to_string()),
+        percent_cell(
+            format!("{:.2}/{} cores", usage.cpu_cores, info.cpus),
+            usage.cpu_percent(info),
+        ),
+        percent_cell(
+            format!(
+                "{}/{}",
+                format_memory(usage.memory_used_bytes),
+                format_memory(info.memory_total_bytes)
+            ),
+            usage.memory_percent(info),
+        ),
+        Cell::from(
+            info.disk_usage
+                .map_or_else(|| "-".to_string(), |disk| format_bytes(disk.total())),
+        ),
+    ])
+}
+
+/// Summary of a host's container usage against its capacity, container counts and engine
+fn host_summary_line<'a>(
+    host_id: &'a str,
+    info: Option<&'a Result<HostInfo, String>>,
+    usage: HostUsage,
+    styles: &UiStyles,
+) -> Line<'a> {
+    let mut spans = vec![Span::styled(format!("{:<20}", host_id), styles.header)];
+
+    let info = match info {
+        Some(Ok(info)) => info,
+        Some(Err(error)) => {
+            spans.push(Span::styled(
+                format!("Host info unavailable: {}", error),
+                styles.high,
+            ));
+            return Line::from(spans);
+        }
+        None => {
+            spans.push(Span::raw("Loading host info..."));
+            return Line::from(spans);
+        }
+    };
+
+    let percent_span = |percent: Option<f64>| match percent {
+        Some(percent) => Span::styled(
+            format!(" {:>5.1}%", percent),
+            get_percentage_style(percent, styles),
+        ),
+        None => Span::raw(""),
+    };
+
+    spans.extend([
+        Span::raw(format!("CPU {:.2} / {} cores", usage.cpu_cores, info.cpus)),
+        percent_span(usage.cpu_percent(info)),
+        Span::raw(format!(
+            "   Mem {} / {}",
+            format_memory(usage.memory_used_bytes),
+            format_memory(info.memory_total_bytes)
+        )),
+        percent_span(usage.memory_percent(info)),
+        Span::raw(format!(
+            "   {} running, {} paused, {} stopped   Docker {} on {}",
+            info.containers_running,
+            info.containers_paused,
+            info.containers_stopped,
+            info.engine_version,
+            info.operating_system
+        )),
+    ]);
+
+    Line::from(spans)
+}
+
+/// Renders the log view for a specific container
+fn render_log_view(
+    f: &mut Frame,
+    container_key: &ContainerKey,
+    state: &mut AppState,
+    styles: &UiStyles,
+) {
+    let size = f.area();
+
+    // Get container info
+    let container_name = state
+        .containers
+        .get(container_key)
+        .map(|c| c.name.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    // Get logs for this container (only if it matches current_logs)
+    let (log_lines, num_lines) = if let Some((key, logs)) = &state.current_logs {
+        if key == container_key {
+            // Format log entries into styled lines with colored timestamps
+            let lines: Vec<Line> = logs
+                .iter()
+                .map(|entry| {
+                    let timestamp_str = entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
+                    Line::from(vec![
+                        Span::styled(timestamp_str, styles.timestamp),
+                        Span::raw(" "),
+                        Span::raw(entry.message.clone()),
+                    ])
+                })
+                .collect();
+            let num_lines = lines.len();
+            (lines, num_lines)
+        } else {
+            (Vec::new(), 0)
+        }
+    } else {
+        (Vec::new(), 0)
+    };
+
+    // Calculate visible height (subtract 2 for borders)
+    let visible_height = size.height.saturating_sub(2) as usize;
+    let actual_scroll = resolve_scroll(state, num_lines, visible_height);
+
+    // Create log widget with scrolling
+    let log_widget = Paragraph::new(log_lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    "Logs: {} ({}) - Press ESC to return {}",
+                    container_name,
+                    container_key.host_id,
+                    if state.is_at_bottom {
+                        "[AUTO]"
+                    } else {
+                        "[MANUAL]"
+                    }
+                ))
+                .style(styles.border),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((actual_scroll as u16, 0));
+
+    f.render_widget(log_widget, size);
+}
+
+/// Renders the output of a one-off exec command for a specific container
+fn render_exec_view(
+    f: &mut Frame,
+    container_key: &ContainerKey,
+    state: &mut AppState,
+    styles: &UiStyles,
+) {
+    let size = f.area();
+
+    let container_name = state
+        .containers
+        .get(container_key)
+        .map(|c| c.name.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let Some(session) = state
+        .current_exec
+        .as_ref()
+        .filter(|session| &session.key == container_key)
+    else {
+        return;
+    };
+
+    // stderr is highlighted so errors stand out from regular output
+    let mut lines: Vec<Line> = session
+        .lines
+        .iter()
+        .map(|line| match line.stream {
+            ExecStream::Stdout => Line::raw(line.text.clone()),
+            ExecStream::Stderr => Line::styled(line.text.clone(), styles.high),
+        })
+        .collect();
+
+    let status = match &session.status {
+        None => format!("running {:.1}s", session.started_at.elapsed().as_secs_f64()),
+        Some(ExecStatus::Exited {
+            exit_code,
+            duration,
+        }) => {
+            let exit_code = exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "?".to_string());
+            format!("exit {} in {:.1}s", exit_code, duration.as_secs_f64())
+        }
+        Some(ExecStatus::Failed(error)) => {
+            lines.push(Line::styled(error.clone(), styles.high));
+            "failed".to_string()
+        }
+    };
+
+    let status_style = match &session.status {
+        None => styles.medium,
+        Some(ExecStatus::Exited {
+            exit_code: Some(0), ..
+        }) => styles.low,
+        Some(_) => styles.high,
+    };
+
+    let title = Line::from(vec![
+        Span::raw(format!(
+            "$ {} - {} ({}) [",
+            session.command, container_name, container_key.host_id
+        )),
+        Span::styled(status, status_style),
+        Span::raw("] - 'x' to run another, ESC to return"),
+    ]);
+
+    let visible_height = size.height.saturating_sub(2) as usize;
+    let actual_scroll = resolve_scroll(state, lines.len(), visible_height);
+
+    let output_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(styles.border),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((actual_scroll as u16, 0));
+
+    f.render_widget(output_widget, size);
+}
+
+/// Renders the processes running in a container as a sortable table
+fn render_process_view(
+    f: &mut Frame,
+    list: &ProcessList,
+    container_name: &str,
+    styles: &UiStyles,
+    table_state: &mut TableState,
+) {
+    let size = f.area();
+
+    let rows: Vec<Row> = list
+        .processes
+        .iter()
+        .map(|process| {
+            Row::new(vec![
+                Cell::from(process.pid.to_string()),
+                Cell::from(process.user.as_str()),
+                Cell::from(format!("{:.1}", process.cpu))
+                    .style(get_percentage_style(process.cpu, styles)),
+                Cell::from(format!("{:.1}", process.memory))
+                    .style(get_percentage_style(process.memory, styles)),
+                Cell::from(process.command.as_str()),
+            ])
+        })
+        .collect();
+
+    // Mark the sorted column with its direction
+    let arrow = if list.descending { "▼" } else { "▲" };
+    let headers = [
+        (ProcessSort::Pid, "PID"),
+        (ProcessSort::User, "User"),
+        (ProcessSort::Cpu, "CPU %"),
+        (ProcessSort::Memory, "Mem %"),
+        (ProcessSort::Command, "Command"),
+    ]
+    .map(|(sort, title)| {
+        if sort == list.sort {
+            format!("{} {}", title, arrow)
+        } else {
+            title.to_string()
+        }
+    });
+    let header = Row::new(headers).style(styles.header).bottom_margin(1);
+
+    let constraints = [
+        Constraint::Length(8),  // PID
+        Constraint::Length(12), // User
+        Constraint::Length(8),  // CPU %
+        Constraint::Length(8),  // Mem %
+        Constraint::Fill(1),    // Command
+    ];
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Processes: {} ({}) - {} processes - sort with p/u/c/m/n, ESC to return",
+            container_name,
+            list.key.host_id,
+            list.processes.len()
+        ))
+        .style(styles.border);
+
+    if let Some(error) = &list.error {
+        block = block.title_bottom(Line::from(Span::styled(error.clone(), styles.high)));
+    }
+
+    let table = Table::new(rows, constraints)
+        .header(header)
+        .block(block)
+        .row_highlight_style(styles.selected);
+
+    f.render_stateful_widget(table, size, table_state);
+}
+
+/// Renders a container's directory listing, with a preview pane when a file is open
+fn render_file_view(
+    f: &mut Frame,
+    browser: &FileBrowser,
+    container_name: &str,
+    styles: &UiStyles,
+    table_state: &mut TableState,
+) {
+    let size = f.area();
+
+    // The preview takes the right half of the screen while open
+    let (list_area, preview_area) = if browser.preview.is_some() {
+        let [list, preview] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(size);
+        (list, Some(preview))
+    } else {
+        (size, None)
+    };
+
+    let rows: Vec<Row> = browser
+        .entries
+        .iter()
+        .map(|entry| {
+            let (name, kind, size) = match &entry.kind {
+                FileKind::Directory => (format!("{}/", entry.name), "dir", String::new()),
+                FileKind::File => (entry.name.clone(), "file", format_bytes(entry.size)),
+                FileKind::Symlink(target) => (
+                    format!("{} -> {}", entry.name, target),
+                    "link",
+                    String::new(),
+                ),
+                FileKind::Other => (entry.name.clone(), "other", String::new()),
+            };
+
+            let style = match entry.kind {
+                FileKind::Directory => styles.header,
+                _ => Style::default(),
+            };
+
+            Row::new(vec![
+                Cell::from(name).style(style),
+                Cell::from(kind),
+                Cell::from(size),
+            ])
+        })
+        .collect();
+
+    let header = Row::new(vec!["Name", "Type", "Size"])
+        .style(styles.header)
+        .bottom_margin(1);
+
+    let constraints = [
+        Constraint::Fill(1),    // Name
+        Constraint::Length(6),  // Type
+        Constraint::Length(10), // Size
+    ];
+
+    let mut title = format!(
+        "Files: {} ({}) - {}",
+        container_name, browser.key.host_id, browser.path
+    );
+    if browser.loading {
+        title.push_str(" - loading...");
+    }
+    title.push_str(" - Enter to open, Backspace up, 'g' go to path, 'd' download, ESC to return");
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(styles.border);
+
+    if let Some(error) = &browser.error {
+        block = block.title_bottom(Line::from(Span::styled(error.clone(), styles.high)));
+    } else if let Some(message) = &browser.message {
+        block = block.title_bottom(Line::from(Span::styled(message.clone(), styles.low)));
+    } else if browser.truncated {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(
+                "Listing truncated - only {} entries are shown, press 'g' to open a path directly",
+                browser.entries.len()
+            ),
+            styles.medium,
+        )));
+    }
+
+    let table = Table::new(rows, constraints)
+        .header(header)
+        .block(block)
+        .row_highlight_style(styles.selected);
+
+    f.render_stateful_widget(table, list_area, table_state);
+
+    if let (Some(area), Some((path, preview))) = (preview_area, &browser.preview) {
+        let mut title = format!("{} ({})", path, format_bytes(preview.size));
+        if preview.truncated {
+            title.push_str(&format!(
+                " - first {}",
+                format_bytes(preview.text.len() as u64)
+            ));
+        }
+        title.push_str(" - ESC to close");
+
+        let text = if preview.binary {
+            Text::from(Span::styled(
+                "Binary file - press 'd' to download",
+                styles.medium,
+            ))
+        } else {
+            Text::from(preview.text.as_str())
+        };
+
+        let preview_widget = Paragraph::new(text).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(styles.border),
+        );
+
+        f.render_widget(preview_widget, area);
+    }
+}
+
+/// A metric plotted in a chart
+struct ChartSeries {
+    name: &'static str,
+    metric: fn(&HistorySample) -> f64,
+}
+
+/// A chart of one or more metrics sharing a unit
+struct MetricChart {
+    title: &'static str,
+    series: Vec<ChartSeries>,
+    /// Current limit, drawn as a reference line
+    limit: Option<f64>,
+    format_value: fn(f64) -> String,
+}
+
+/// Renders full-screen charts of a container's CPU, memory, network and block I/O history
+fn render_chart_view(
+    f: &mut Frame,
+    container_key: &ContainerKey,
+    state: &AppState,
+    styles: &UiStyles,
+) {
+    let size = f.area();
+    let window = CHART_WINDOWS[state.chart_window];
+
+    let container = state.containers.get(container_key);
+    let container_name = container.map(|c| c.name.as_str()).unwrap_or("Unknown");
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Charts: {} ({}) - last {} - 'w' to change window, ESC to return",
+            container_name,
+            container_key.host_id,
+            format_window(window)
+        ))
+        .style(styles.border);
+    let inner = outer.inner(size);
+    f.render_widget(outer, size);
+
+    let (Some(container), Some(history)) = (container, state.stats_history.get(container_key))
+    else {
+        f.render_widget(Paragraph::new("No stats received yet"), inner);
+        return;
+    };
+
+    let [top, bottom] =
+        Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(inner);
+    let [cpu_area, memory_area] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(top);
+    let [network_area, block_area] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(bottom);
+
+    let stats = &container.stats;
+    let charts = [
+        (
+            cpu_area,
+            MetricChart {
+                title: "CPU (% of one core)",
+                series: vec![ChartSeries {
+                    name: "cpu",
+                    metric: |s| s.cpu,
+                }],
+                limit: (stats.cpu_limit_cores > 0.0).then_some(stats.cpu_limit_cores * 100.0),
+                format_value: |value| format!("{:.1}%", value),
+            },
+        ),
+        (
+            memory_area,
+            MetricChart {
+                title: "Memory",
+                series: vec![ChartSeries {
+                    name: "used",
+                    metric: |s| s.memory_used_bytes,
+                }],
+                limit: stats.memory_limit_bytes.map(|limit| limit as f64),
+                format_value: |value| format_memory(value as u64),
+            },
+        ),
+        (
+            network_area,
+            MetricChart {
+                title: "Network",
+                series: vec![
+                    ChartSeries {
+                        name: "rx",
+                        metric: |s| s.network_rx_bytes_per_sec,
+                    },
+                    ChartSeries {
+                        name: "tx",
+                        metric: |s| s.network_tx_bytes_per_sec,
+                    },
+                ],
+                limit: None,
+                format_value: format_bytes_per_sec,
+            },
+        ),
+        (
+            block_area,
+            MetricChart {
+                title: "Block I/O",
+                series: vec![
+                    ChartSeries {
+                        name: "read",
+                        metric: |s| s.block_read_bytes_per_sec,
+                    },
+                    ChartSeries {
+                        name: "write",
+                        metric: |s| s.block_write_bytes_per_sec,
+                    },
+                ],
+                limit: None,
+                format_value: format_bytes_per_sec,
+            },
+        ),
+    ];
+
+    let now = Instant::now();
+    for (area, chart) in charts {
+        render_metric_chart(f, area, &chart, history, window, now, styles);
+    }
+}
+
+/// Renders one chart with its series, the limit as a reference line and
+/// min/avg/max/p95 summaries of each series below it
+fn render_metric_chart(
+    f: &mut Frame,
+    area: Rect,
+    chart: &MetricChart,
+    history: &StatsHistory,
+    window: Duration,
+    now: Instant,
+    styles: &UiStyles,
+) {
+    let window_secs = window.as_secs_f64();
+    let format_value = chart.format_value;
+    let points: Vec<Vec<(f64, f64)>> = chart
+        .series
+        .iter()
+        .map(|s| history.points(window, now, s.metric))
+        .collect();
+    let limit_points = chart
+        .limit
+        .map(|limit| [(-window_secs, limit), (0.0, limit)]);
+
+    // Scale to the largest value, including the limit so the reference line is visible
+    let y_max = points
+        .iter()
+        .flatten()
+        .map(|&(_, y)| y)
+        .chain(chart.limit)
+        .fold(0.0_f64, f64::max);
+    let y_max = if y_max > 0.0 { y_max * 1.1 } else { 1.0 };
+
+    let mut datasets: Vec<Dataset> = chart
+        .series
+        .iter()
+        .zip(&points)
+        .zip(styles.chart_series)
+        .map(|((s, data), style)| {
+            Dataset::default()
+                .name(s.name)
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(style)
+                .data(data)
+        })
+        .collect();
+
+    if let Some(data) = &limit_points {
+        datasets.push(
+            Dataset::default()
+                .name("limit")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(styles.chart_limit)
+                .data(data),
+        );
+    }
+
+    let summaries: Vec<Span> = chart
+        .series
+        .iter()
+        .zip(&points)
+        .filter_map(|(s, data)| {
+            let values: Vec<f64> = data.iter().map(|&(_, y)| y).collect();
+            summarize(&values)
+                .map(|summary| Span::raw(format_summary(s.name, &summary, format_value)))
+        })
+        .collect();
+
+    let x_labels = [format!("-{}", format_window(window)), "now".to_string()];
+    let y_labels = [
+        format_value(0.0),
+        format_value(y_max / 2.0),
+        format_value(y_max),
+    ];
+
+    let chart_widget = Chart::new(datasets)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(chart.title)
+                .title_bottom(Line::from(summaries))
+                .style(styles.border),
+        )
+        .x_axis(Axis::default().bounds([-window_secs, 0.0]).labels(x_labels))
+        .y_axis(Axis::default().bounds([0.0, y_max]).labels(y_labels));
+
+    f.render_widget(chart_widget, area);
+}
+
+/// Formats a series summary as "name min .. avg .. max .. p95 .. "
+fn format_summary(name: &str, summary: &Summary, format_value: fn(f64) -> String) -> String {
+    format!(
+        " {} min {} avg {} max {} p95 {} ",
+        name,
+        format_value(summary.min),
+        format_value(summary.avg),
+        format_value(summary.max),
+        format_value(summary.p95)
+    )
+}
+
+/// Formats a chart time window as minutes, or seconds below a minute
+fn format_window(window: Duration) -> String {
+    let secs = window.as_secs();
+    if secs >= 60 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Renders live details of a container, updated from its stats stream
+fn render_detail_view(
+    f: &mut Frame,
+    container_key: &ContainerKey,
+    state: &mut AppState,
+    styles: &UiStyles,
+) {
+    let size = f.area();
+
+    let lines = match state.containers.get(container_key) {
+        Some(container) => detail_lines(container, state.current_health_log.as_ref(), styles),
+        None => vec![Line::from("Container is no longer running")],
+    };
+
+    // Clamp scrolling to the content (subtract 2 for borders)
+    let visible_height = size.height.saturating_sub(2) as usize;
+    let max_scroll = lines.len().saturating_sub(visible_height);
+    state.detail_scroll_offset = state.detail_scroll_offset.min(max_scroll);
+
+    let container_name = state
+        .containers
+        .get(container_key)
+        .map(|c| c.name.as_str())
+        .unwrap_or("Unknown");
+
+    let detail_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    "Details: {} ({}) - ↑/↓ to scroll, ESC to return",
+                    container_name, container_key.host_id
+                ))
+                .style(styles.border),
+        )
+        .scroll((state.detail_scroll_offset as u16, 0));
+
+    f.render_widget(detail_widget, size);
+}
+
+/// Builds the sections of the detail view for a container
+fn detail_lines<'a>(
+    container: &'a Container,
+    health_log: Option<&'a Result<Vec<HealthCheck>, String>>,
+    styles: &UiStyles,
+) -> Vec<Line<'a>> {
+    let stats = &container.stats;
+    let (pids, pids_style) = format_pids(stats, styles);
+
+    let mut lines = vec![
+        Line::from(Span::styled("Container", styles.header)),
+        detail_row("ID", Span::raw(container.id.as_str())),
+        detail_row("Name", Span::raw(container.name.as_str())),
+        detail_row("Host", Span::raw(container.host_id.as_str())),
+        detail_row("Status", Span::raw(container.status.as_str())),
+        detail_row(
+            "Health",
+            Span::styled(
+                container.health.label(),
+                health_style(container.health, styles),
+            ),
+        ),
+        detail_row(
+            "Restarted",
+            Span::raw(
+                container
+                    .lifecycle
+                    .restarted_at
+                    .map_or("-".to_string(), |time| time.format("%H:%M:%S").to_string()),
+            ),
+        ),
+        detail_row(
+            "Last signal",
+            Span::raw(container.lifecycle.last_signal.as_deref().unwrap_or("-")),
+        ),
+        detail_row(
+            "Out of memory",
+            if container.lifecycle.out_of_memory {
+                Span::styled("process killed (OOM)", styles.high)
+            } else {
+                Span::raw("no")
+            },
+        ),
+        Line::default(),
+        Line::from(Span::styled("Resources", styles.header)),
+        detail_row(
+            "CPU",
+            Span::styled(
+                format!("{:.1}%", stats.cpu),
+                get_percentage_style(stats.cpu, styles),
+            ),
+        ),
+        detail_row(
+            "CPU limit",
+            Span::raw(format!("{:.2} cores", stats.cpu_limit_cores)),
+        ),
+        detail_row(
+            "CPU throttling",
+            Span::styled(
+                format!(
+                    "{:.1}% of periods, {:.3}s/s throttled",
+                    stats.cpu_throttled_ratio * 100.0,
+                    stats.cpu_throttled_secs_per_sec
+                ),
+                get_percentage_style(stats.cpu_throttled_ratio * 100.0, styles),
+            ),
+        ),
+        detail_row(
+            "Memory",
+            Span::styled(
+                format!("{} ({:.1}%)", format_memory_usage(stats), stats.memory),
+                get_percentage_style(stats.memory, styles),
+            ),
+        ),
+        detail_row(
+            "Network",
+            Span::raw(format!(
+                "TX {}  RX {}",
+                format_bytes_per_sec(stats.network_tx_bytes_per_sec),
+                format_bytes_per_sec(stats.network_rx_bytes_per_sec)
+            )),
+        ),
+        detail_row(
+            "Disk",
+            Span::raw(format!(
+                "read {}  write {}",
+                format_bytes_per_sec(stats.block_read_bytes_per_sec),
+                format_bytes_per_sec(stats.block_write_bytes_per_sec)
+            )),
+        ),
+        detail_row("PIDs", Span::styled(pids, pids_style)),
+        Line::default(),
+        Line::from(Span::styled("Memory breakdown", styles.header)),
+    ];
+
+    let breakdown = &stats.memory_breakdown;
+    let bytes = |value: Option<u64>| Span::raw(value.map_or("n/a".to_string(), format_memory));
+    let count = |value: Option<u64>| Span::raw(value.map_or("n/a".to_string(), |v| v.to_string()));
+
+    lines.extend([
+        detail_row("Anonymous (RSS)", bytes(breakdown.anon)),
+        detail_row("Page cache", bytes(breakdown.file)),
+        detail_row("Kernel", bytes(breakdown.kernel)),
+        detail_row("Shared memory", bytes(breakdown.shmem)),
+        detail_row("Swap", bytes(breakdown.swap)),
+        detail_row("Page faults", count(breakdown.pgfault)),
+        detail_row("Major page faults", count(breakdown.pgmajfault)),
+    ]);
+
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled(
+        "Network interfaces",
+        styles.header,
+    )));
+
+    if stats.network_interfaces.is_empty() {
+        lines.push(Line::from("  No network interfaces"));
+    }
+
+    for interface in &stats.network_interfaces {
+        let errors = interface.rx_errors + interface.tx_errors;
+        let drops = interface.rx_dropped + interface.tx_dropped;
+        let problem_style = if errors + drops > 0 {
+            styles.high
+        } else {
+            Style::default()
+        };
+
+        lines.push(Line::from(vec![
+            Span::raw(format!("  {:<20}", interface.name)),
+            Span::raw(format!(
+                "RX {} ({:.0} pkt/s)  TX {} ({:.0} pkt/s)  ",
+                format_bytes_per_sec(interface.rx_bytes_per_sec),
+                interface.rx_packets_per_sec,
+                format_bytes_per_sec(interface.tx_bytes_per_sec),
+                interface.tx_packets_per_sec
+            )),
+            Span::styled(
+                format!(
+                    "errors {}/{}  drops {}/{} (rx/tx)",
+                    interface.rx_errors,
+                    interface.tx_errors,
+                    interface.rx_dropped,
+                    interface.tx_dropped
+                ),
+                problem_style,
+            ),
+        ]));
+    }
+
+    if container.health != Health::None {
+        lines.push(Line::default());
+        lines.extend(health_check_lines(health_log, styles));
+    }
+
+    lines
+}
+
+/// Builds the health checks section of the detail view, most recent check first
+fn health_check_lines<'a>(
+    health_log: Option<&'a Result<Vec<HealthCheck>, String>>,
+    styles: &UiStyles,
+) -> Vec<Line<'a>> {
+    let mut lines = vec![Line::from(Span::styled("Health checks", styles.header))];
+
+    let checks = match health_log {
+        None => {
+            lines.push(Line::from("  Loading..."));
+            return lines;
+        }
+        Some(Err(error)) => {
+            lines.push(Line::from(Span::styled(
+                format!("  Error: {}", error),
+                styles.high,
+            )));
+            return lines;
         }
+        Some(Ok(checks)) if checks.is_empty() => {
+            lines.push(Line::from("  No health checks have run yet"));
+            return lines;
+        }
+        Some(Ok(checks)) => checks,
+    };
+
+    for check in checks.iter().rev() {
+        let (exit_code, style) = match check.exit_code {
+            Some(0) => ("exit 0".to_string(), styles.low),
+            Some(code) => (format!("exit {}", code), styles.high),
+            None => ("exit ?".to_string(), styles.medium),
+        };
+
+        lines.push(Line::from(vec![
+            Span::raw(format!(
+                "  {} → {}  ",
+                check.start.as_deref().unwrap_or("?"),
+                check.end.as_deref().unwrap_or("?")
+            )),
+            Span::styled(exit_code, style),
+        ]));
+        lines.extend(
+            check
+                .output
+                .lines()
+                .map(|line| Line::from(format!("    {}", line))),
+        );
     }
+
+    lines
 }
 
-/// Renders the container list view
-fn render_container_list(
+/// Formats a label and value as an aligned line in the detail view
+fn detail_row<'a>(label: &str, value: Span<'a>) -> Line<'a> {
+    Line::from(vec![Span::raw(format!("  {:<20}", label)), value])
+}
+
+/// Renders the writable layer changes of a container, grouped by directory
+fn render_changes_view(
     f: &mut Frame,
-    containers: &HashMap<ContainerKey, Container>,
-    sorted_container_keys: &[ContainerKey],
+    changes: Option<&Result<ContainerChanges, String>>,
+    container_name: &str,
+    host_id: &str,
     styles: &UiStyles,
     table_state: &mut TableState,
-    show_host_column: bool,
 ) {
     let size = f.area();
 
-    // Use pre-sorted list instead of sorting every frame
-    let rows: Vec<Row> = sorted_container_keys
+    let mut title = format!("Changes: {} ({})", container_name, host_id);
+    let mut rows: Vec<Row> = Vec::new();
+
+    match changes {
+        None => title.push_str(" - loading..."),
+        Some(Err(_)) => {}
+        Some(Ok(changes)) => {
+            let size_rw = changes.size_rw.map_or("unknown".to_string(), |size| {
+                format_bytes(size.max(0) as u64)
+            });
+            title.push_str(&format!(
+                " - {} paths, writable layer {}",
+                changes.total(),
+                size_rw
+            ));
+
+            for group in &changes.groups {
+                let counts = format!("+{} ~{} -{}", group.added, group.modified, group.deleted);
+                rows.push(Row::new(vec![
+                    Cell::from(""),
+                    Cell::from(group.directory.as_str()).style(styles.header),
+                    Cell::from(counts).style(styles.header),
+                ]));
+
+                for change in &group.changes {
+                    let style = match change.kind {
+                        ChangeKind::Added => styles.low,
+                        ChangeKind::Modified => styles.medium,
+                        ChangeKind::Deleted => styles.high,
+                    };
+                    rows.push(Row::new(vec![
+                        Cell::from(change.kind.marker()).style(style),
+                        Cell::from(format!("  {}", change.path)),
+                        Cell::from(""),
+                    ]));
+                }
+            }
+        }
+    }
+    title.push_str(" - 'r' to refresh, ESC to return");
+
+    let header = Row::new(vec!["", "Path", "Changes"])
+        .style(styles.header)
+        .bottom_margin(1);
+
+    let constraints = [
+        Constraint::Length(2),  // Kind
+        Constraint::Fill(1),    // Path
+        Constraint::Length(16), // Counts
+    ];
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(styles.border);
+
+    match changes {
+        Some(Err(error)) => {
+            block = block.title_bottom(Line::from(Span::styled(error.clone(), styles.high)));
+        }
+        Some(Ok(changes)) if changes.groups.is_empty() => {
+            block = block.title_bottom(Line::from(Span::styled(
+                "No changes in the writable layer",
+                styles.low,
+            )));
+        }
+        _ => {}
+    }
+
+    let table = Table::new(rows, constraints)
+        .header(header)
+        .block(block)
+        .row_highlight_style(styles.selected);
+
+    f.render_stateful_widget(table, size, table_state);
+}
+
+/// Renders the active prompt as a single-line input box at the bottom of the screen
+fn render_prompt(f: &mut Frame, prompt: &Prompt, container_name: &str, styles: &UiStyles) {
+    let [_, area] = Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).areas(f.area());
+
+    let (marker, title) = match &prompt.action {
+        PromptAction::Exec => (
+            "$ ",
+            format!(
+                "Run in {} - Enter to run, ↑/↓ for history, ESC to cancel",
+                container_name
+            ),
+        ),
+        PromptAction::OpenPath => (
+            "> ",
+            format!(
+                "Open path in {} (absolute or relative to the listed directory) - Enter to open, ESC to cancel",
+                container_name
+            ),
+        ),
+        PromptAction::Download { path } => (
+            "> ",
+            format!(
+                "Download {} from {} to (end with .tar to keep the archive) - Enter to save, ESC to cancel",
+                path, container_name
+            ),
+        ),
+    };
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(marker, styles.header),
+        Span::raw(prompt.input.as_str()),
+        Span::styled("█", styles.border),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(styles.border),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(input, area);
+}
+
+/// Keys of the container list and what they do, shown by the key help
+const KEY_HELP: &[(&str, &str)] = &[
+    ("↑/↓", "Select a container or group"),
+    ("Enter", "Show logs, or expand/collapse a group"),
+    ("s", "Open a shell"),
+    ("x", "Run a command"),
+    ("a", "Attach"),
+    ("t", "Show processes"),
+    ("f", "Browse files"),
+    ("c", "Show filesystem changes"),
+    ("i", "Show details"),
+    ("g", "Show charts"),
+    ("n", "Toggle CPU mode"),
+    ("m", "Cycle smoothing"),
+    ("h", "Show hosts"),
+    ("v", "Show the fleet"),
+    ("p", "Group by Compose project or host"),
+    ("R/S", "Restart or stop the Compose project or service"),
+    ("U", "Start the last stopped Compose project or service"),
+    ("ESC", "List all hosts again after picking one"),
+    ("q", "Quit"),
+];
+
+/// Renders the key help in the middle of the screen
+fn render_key_help(f: &mut Frame, styles: &UiStyles) {
+    let key_width = KEY_HELP
         .iter()
-        .filter_map(|key| containers.get(key))
-        .map(|c| create_container_row(c, styles, show_host_column))
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+    let lines: Vec<Line> = KEY_HELP
+        .iter()
+        .map(|(key, description)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<width$}  ", key, width = key_width),
+                    styles.header,
+                ),
+                Span::raw(*description),
+            ])
+        })
         .collect();
 
-    let header = create_header_row(styles, show_host_column);
-    let table = create_table(rows, header, containers.len(), styles, show_host_column);
+    let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4;
+    let height = lines.len() as u16 + 2;
+    let [_, area, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(height),
+        Constraint::Fill(1),
+    ])
+    .areas(f.area());
+    let [_, area, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(width),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
 
-    f.render_stateful_widget(table, size, table_state);
+    let help = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Keys - press any key to close")
+            .style(styles.border),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(help, area);
 }
 
-/// Renders the log view for a specific container
-fn render_log_view(
+/// Renders the question confirming a Compose action at the bottom of the screen
+fn render_compose_confirmation(
     f: &mut Frame,
-    container_key: &ContainerKey,
-    state: &mut AppState,
+    target: &ComposeTarget,
+    action: ComposeAction,
     styles: &UiStyles,
 ) {
-    let size = f.area();
+    let [_, area] = Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).areas(f.area());
 
-    // Get container info
-    let container_name = state
-        .containers
-        .get(container_key)
-        .map(|c| c.name.as_str())
-        .unwrap_or("Unknown");
+    let question = Paragraph::new(Line::from(vec![
+        Span::styled(format!("{} ", action.verb()), styles.high),
+        Span::raw(format!(
+            "all containers of {} on {}? ",
+            target, target.host_id
+        )),
+        Span::styled("y", styles.header),
+        Span::raw(" to confirm, "),
+        Span::styled("n", styles.header),
+        Span::raw(" or ESC to cancel"),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Confirm")
+            .style(styles.border),
+    );
 
-    // Get logs for this container (only if it matches current_logs)
-    let (log_lines, num_lines) = if let Some((key, logs)) = &state.current_logs {
-        if key == container_key {
-            // Format log entries into styled lines with colored timestamps
-            let lines: Vec<Line> = logs
-                .iter()
-                .map(|entry| {
-                    let timestamp_str = entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
-                    Line::from(vec![
-                        Span::styled(timestamp_str, styles.timestamp),
-                        Span::raw(" "),
-                        Span::raw(entry.message.clone()),
-                    ])
-                })
-                .collect();
-            let num_lines = lines.len();
-            (lines, num_lines)
-        } else {
-            (Vec::new(), 0)
-        }
+    f.render_widget(Clear, area);
+    f.render_widget(question, area);
+}
+
+/// Renders the progress of a Compose action per container at the bottom of the screen
+fn render_compose_operation(f: &mut Frame, operation: &ComposeOperation, styles: &UiStyles) {
+    let mut lines: Vec<Line> = operation
+        .steps
+        .iter()
+        .map(|step| {
+            let (marker, style, detail) = match &step.status {
+                StepStatus::Pending => ("·", styles.border, ""),
+                StepStatus::Running => ("…", styles.medium, ""),
+                StepStatus::Done => ("✓", styles.low, ""),
+                StepStatus::Failed(error) => ("✗", styles.high, error.as_str()),
+            };
+            Line::from(vec![
+                Span::styled(format!("{} ", marker), style),
+                Span::raw(step.name.as_str()),
+                Span::styled(format!(" {}", detail), styles.high),
+            ])
+        })
+        .collect();
+
+    if let Some(error) = &operation.error {
+        lines.push(Line::styled(error.as_str(), styles.high));
+    } else if operation.finished && operation.steps.is_empty() {
+        lines.push(Line::raw("No containers found"));
+    }
+
+    let status = if !operation.finished {
+        "running".to_string()
+    } else if operation.error.is_some() {
+        "failed - ESC to close".to_string()
+    } else if operation.failures() > 0 {
+        format!(
+            "failed on {} of {} containers - ESC to close",
+            operation.failures(),
+            operation.steps.len()
+        )
     } else {
-        (Vec::new(), 0)
+        "done - ESC to close".to_string()
     };
 
-    // Calculate visible height (subtract 2 for borders)
-    let visible_height = size.height.saturating_sub(2) as usize;
+    // Leave the top of the container list visible
+    let height = (lines.len() as u16 + 2).min(f.area().height / 2).max(3);
+    let [_, area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(height)]).areas(f.area());
+
+    let progress = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(
+                "{} {} on {} - {}",
+                operation.action.verb(),
+                operation.target,
+                operation.target.host_id,
+                status
+            ))
+            .style(styles.border),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(progress, area);
+}
 
+/// Clamps the shared scroll state to the content and returns the offset to render
+/// Follows the bottom of the content while the user hasn't scrolled away from it
+fn resolve_scroll(state: &mut AppState, num_lines: usize, visible_height: usize) -> usize {
     // Calculate max scroll position
-    let max_scroll = if num_lines > visible_height {
-        num_lines.saturating_sub(visible_height)
-    } else {
-        0
-    };
+    let max_scroll = num_lines.saturating_sub(visible_height);
 
     // Determine actual scroll offset
     let actual_scroll = if state.is_at_bottom {
@@ -155,68 +1635,270 @@ fn render_log_view(
     // Update scroll offset to actual (for proper clamping)
     state.log_scroll_offset = actual_scroll;
 
-    // Create log widget with scrolling
-    let log_widget = Paragraph::new(log_lines)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(format!(
-                    "Logs: {} ({}) - Press ESC to return {}",
-                    container_name,
-                    container_key.host_id,
-                    if state.is_at_bottom {
-                        "[AUTO]"
-                    } else {
-                        "[MANUAL]"
-                    }
-                ))
-                .style(styles.border),
-        )
-        .wrap(Wrap { trim: false })
-        .scroll((actual_scroll as u16, 0));
+    actual_scroll
+}
 
-    f.render_widget(log_widget, size);
+/// A column of the container list
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Column {
+    Id,
+    Name,
+    Host,
+    Cpu,
+    CpuTrend,
+    Throttled,
+    Memory,
+    MemoryTrend,
+    MemoryUsed,
+    NetworkTx,
+    NetworkRx,
+    DiskRead,
+    DiskWrite,
+    Pids,
+    Health,
+    Status,
+}
+
+/// All columns of the container list, in display order
+const COLUMNS: [Column; 16] = [
+    Column::Id,
+    Column::Name,
+    Column::Host,
+    Column::Cpu,
+    Column::CpuTrend,
+    Column::Throttled,
+    Column::Memory,
+    Column::MemoryTrend,
+    Column::MemoryUsed,
+    Column::NetworkTx,
+    Column::NetworkRx,
+    Column::DiskRead,
+    Column::DiskWrite,
+    Column::Pids,
+    Column::Health,
+    Column::Status,
+];
+
+/// Narrowest the Name column gets before secondary columns are dropped
+const NAME_MIN_WIDTH: u16 = 20;
+
+impl Column {
+    fn title(self) -> &'static str {
+        match self {
+            Column::Id => "ID",
+            Column::Name => "Name",
+            Column::Host => "Host",
+            Column::Cpu => "CPU %",
+            Column::CpuTrend | Column::MemoryTrend => "Trend",
+            Column::Throttled => "Throttled",
+            Column::Memory => "Memory %",
+            Column::MemoryUsed => "Mem Used",
+            Column::NetworkTx => "Net TX",
+            Column::NetworkRx => "Net RX",
+            Column::DiskRead => "Disk R",
+            Column::DiskWrite => "Disk W",
+            Column::Pids => "PIDs",
+            Column::Health => "Health",
+            Column::Status => "Status",
+        }
+    }
+
+    /// Fixed width of the column (the minimum width for Name)
+    fn width(self) -> u16 {
+        match self {
+            Column::Id => 12,
+            Column::Name => NAME_MIN_WIDTH,
+            Column::Host => 20,
+            Column::Cpu | Column::Memory => 28, // Progress bar (20 chars + " 100.0%")
+            Column::CpuTrend | Column::MemoryTrend => SPARKLINE_WIDTH as u16,
+            Column::Throttled => 10,  // ⚠ 100%
+            Column::MemoryUsed => 22, // 512.0MiB / 2.00GiB
+            Column::NetworkTx | Column::NetworkRx | Column::DiskRead | Column::DiskWrite => 12, // 1.23MB/s
+            Column::Pids => 12,   // current/limit
+            Column::Health => 10, // unhealthy
+            Column::Status => 15,
+        }
+    }
+
+    fn constraint(self) -> Constraint {
+        match self {
+            // Name takes whatever space the other columns leave
+            Column::Name => Constraint::Fill(1),
+            column => Constraint::Length(column.width()),
+        }
+    }
+
+    /// Columns with a higher rank are dropped first when the terminal is too narrow
+    /// (0 is always shown)
+    fn drop_rank(self) -> u8 {
+        match self {
+            Column::Id | Column::Name | Column::Cpu | Column::Memory | Column::Status => 0,
+            Column::Health | Column::Host => 1,
+            Column::NetworkTx | Column::NetworkRx => 2,
+            Column::MemoryUsed => 3,
+            Column::Pids => 4,
+            Column::DiskRead | Column::DiskWrite => 5,
+            Column::Throttled => 6,
+            Column::CpuTrend | Column::MemoryTrend => 7,
+        }
+    }
+}
+
+/// Columns of the container list that fit in `width` while keeping Name at least
+/// NAME_MIN_WIDTH wide, dropping secondary columns (trends first) on narrow terminals
+fn visible_columns(width: u16, show_host_column: bool) -> Vec<Column> {
+    let mut columns: Vec<Column> = COLUMNS
+        .into_iter()
+        .filter(|&column| column != Column::Host || show_host_column)
+        .collect();
+
+    // Borders take two characters and columns are separated by one space
+    let required = |columns: &[Column]| -> u16 {
+        columns.iter().map(|c| c.width()).sum::<u16>() + columns.len() as u16 - 1 + 2
+    };
+
+    while required(&columns) > width {
+        let Some(rank) = columns
+            .iter()
+            .map(|c| c.drop_rank())
+            .max()
+            .filter(|&r| r > 0)
+        else {
+            break;
+        };
+        columns.retain(|c| c.drop_rank() != rank);
+    }
+
+    columns
 }
 
 /// Creates a table row for a single container
 fn create_container_row<'a>(
     container: &'a Container,
+    history: Option<&StatsHistory>,
     styles: &UiStyles,
-    show_host_column: bool,
+    columns: &[Column],
+    cpu_mode: CpuMode,
+    grouped: bool,
 ) -> Row<'a> {
-    let cpu_bar = create_progress_bar(container.stats.cpu, 20);
-    let cpu_style = get_percentage_style(container.stats.cpu, styles);
+    let (cpu_value, cpu_max) = cpu_display(&container.stats, cpu_mode);
+    let cpu_bar = create_progress_bar(cpu_value, cpu_max, 20);
+    // Color by how much of the available CPU is used, whatever the display mode
+    let cpu_style = get_percentage_style(cpu_value / cpu_max * 100.0, styles);
 
-    let memory_bar = create_progress_bar(container.stats.memory, 20);
+    let memory_bar = create_progress_bar(container.stats.memory, 100.0, 20);
+
+    // Trends are drawn on the same scale as the bars next to them
+    let (cpu_trend, memory_trend) = match history {
+        Some(history) => {
+            // History keeps per-core values, which are full scale at all available cores
+            let (_, cpu_scale) = cpu_display(&container.stats, CpuMode::PerCore);
+            (
+                sparkline(&history.recent(SPARKLINE_WIDTH, |s| s.cpu), cpu_scale),
+                sparkline(&history.recent(SPARKLINE_WIDTH, |s| s.memory), 100.0),
+            )
+        }
+        None => (String::new(), String::new()),
+    };
     let memory_style = get_percentage_style(container.stats.memory, styles);
 
-    let network_tx = format_bytes_per_sec(container.stats.network_tx_bytes_per_sec);
-    let network_rx = format_bytes_per_sec(container.stats.network_rx_bytes_per_sec);
+    let stats = &container.stats;
+    let cells = columns.iter().map(|column| match column {
+        Column::Id => Cell::from(container.id.as_str()),
+        // Containers are indented below their group header
+        Column::Name if grouped => Cell::from(format!("  {}", container.name)),
+        Column::Name => Cell::from(container.name.as_str()),
+        Column::Host => Cell::from(container.host_id.as_str()),
+        Column::Cpu => Cell::from(cpu_bar.clone()).style(cpu_style),
+        Column::CpuTrend => Cell::from(cpu_trend.clone()).style(cpu_style),
+        Column::Throttled => throttling_cell(stats, styles),
+        Column::Memory => Cell::from(memory_bar.clone()).style(memory_style),
+        Column::MemoryTrend => Cell::from(memory_trend.clone()).style(memory_style),
+        Column::MemoryUsed => Cell::from(format_memory_usage(stats)),
+        Column::NetworkTx => Cell::from(format_bytes_per_sec(stats.network_tx_bytes_per_sec)),
+        Column::NetworkRx => Cell::from(format_bytes_per_sec(stats.network_rx_bytes_per_sec)),
+        Column::DiskRead => Cell::from(format_bytes_per_sec(stats.block_read_bytes_per_sec)),
+        Column::DiskWrite => Cell::from(format_bytes_per_sec(stats.block_write_bytes_per_sec)),
+        Column::Pids => {
+            let (pids, pids_style) = format_pids(stats, styles);
+            Cell::from(pids).style(pids_style)
+        }
+        Column::Health => health_cell(container.health, styles),
+        Column::Status => status_cell(container, styles),
+    });
 
-    let mut cells = vec![
-        Cell::from(container.id.as_str()),
-        Cell::from(container.name.as_str()),
-    ];
+    Row::new(cells)
+}
 
-    if show_host_column {
-        cells.push(Cell::from(container.host_id.as_str()));
+/// Creates the health check cell, colored by status ("-" without a health check)
+fn health_cell(health: Health, styles: &UiStyles) -> Cell<'static> {
+    match health {
+        Health::None => Cell::from("-"),
+        _ => Cell::from(health.label()).style(health_style(health, styles)),
     }
+}
 
-    cells.extend(vec![
-        Cell::from(cpu_bar).style(cpu_style),
-        Cell::from(memory_bar).style(memory_style),
-        Cell::from(network_tx),
-        Cell::from(network_rx),
-        Cell::from(container.status.as_str()),
-    ]);
+/// Creates the status cell - paused containers are medium, and an OOM kill is flagged high
+fn status_cell<'a>(container: &'a Container, styles: &UiStyles) -> Cell<'a> {
+    if container.lifecycle.out_of_memory {
+        Cell::from(format!("{} (OOM)", container.status)).style(styles.high)
+    } else if container.status == "paused" {
+        Cell::from(container.status.as_str()).style(styles.medium)
+    } else {
+        Cell::from(container.status.as_str())
+    }
+}
 
-    Row::new(cells)
+/// Color of a health status - healthy is low, starting medium and unhealthy high
+fn health_style(health: Health, styles: &UiStyles) -> Style {
+    match health {
+        Health::None => Style::default(),
+        Health::Starting => styles.medium,
+        Health::Healthy => styles.low,
+        Health::Unhealthy => styles.high,
+    }
+}
+
+/// Creates the header row of a group with its aggregated usage
+fn create_group_row(group: &GroupSummary, styles: &UiStyles, columns: &[Column]) -> Row<'static> {
+    let marker = if group.collapsed { "▶" } else { "▼" };
+    let title = match &group.key {
+        GroupKey::Host(host_id) => format!("{} - {} containers", host_id, group.containers),
+        GroupKey::Project { project, .. } => format!(
+            "{} - {} services, {} containers",
+            project.as_deref().unwrap_or("(no project)"),
+            group.services,
+            group.containers
+        ),
+    };
+
+    let cells = columns.iter().map(|column| match column {
+        Column::Id => Cell::from(marker),
+        Column::Name => Cell::from(title.clone()),
+        Column::Host => Cell::from(group.key.host_id().clone()),
+        Column::Cpu => Cell::from(format!("{:5.1}%", group.cpu)),
+        Column::MemoryUsed => Cell::from(format_memory(group.memory_used_bytes)),
+        Column::NetworkTx => Cell::from(format_bytes_per_sec(group.network_tx_bytes_per_sec)),
+        Column::NetworkRx => Cell::from(format_bytes_per_sec(group.network_rx_bytes_per_sec)),
+        _ => Cell::from(""),
+    });
+
+    Row::new(cells).style(styles.header)
 }
 
+/// Number of samples (and characters) in the trend sparklines of the container list
+const SPARKLINE_WIDTH: usize = 10;
+
 /// Creates a text-based progress bar with percentage
-fn create_progress_bar(percentage: f64, width: usize) -> String {
-    let percentage = percentage.clamp(0.0, 100.0);
-    let filled_width = ((percentage / 100.0) * width as f64).round() as usize;
+/// The bar is full at `max`, while the label shows the percentage as is
+fn create_progress_bar(percentage: f64, max: f64, width: usize) -> String {
+    let percentage = percentage.max(0.0);
+    let fraction = if max > 0.0 {
+        (percentage / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let filled_width = (fraction * width as f64).round() as usize;
     let empty_width = width.saturating_sub(filled_width);
 
     let bar = format!("{}{}", "█".repeat(filled_width), "░".repeat(empty_width));
@@ -224,6 +1906,97 @@ fn create_progress_bar(percentage: f64, width: usize) -> String {
     format!("{} {:5.1}%", bar, percentage)
 }
 
+/// Returns the CPU percentage to show and the value at which the bar is full
+///
+/// Per core, a container limited to (or running on) 4 cores fills the bar at 400%.
+/// Of limit, usage is divided by the available cores so the bar is full at 100%.
+fn cpu_display(stats: &ContainerStats, cpu_mode: CpuMode) -> (f64, f64) {
+    // No stats received yet - treat as a single core
+    let cores = if stats.cpu_limit_cores > 0.0 {
+        stats.cpu_limit_cores
+    } else {
+        1.0
+    };
+
+    match cpu_mode {
+        CpuMode::PerCore => (stats.cpu, cores * 100.0),
+        CpuMode::OfLimit => (stats.cpu / cores, 100.0),
+    }
+}
+
+/// Shows the share of throttled CPU periods while a container is being throttled
+fn throttling_cell<'a>(stats: &ContainerStats, styles: &UiStyles) -> Cell<'a> {
+    if stats.cpu_throttled_ratio <= 0.0 {
+        return Cell::from("");
+    }
+
+    let percentage = stats.cpu_throttled_ratio * 100.0;
+    Cell::from(format!("⚠ {:.0}%", percentage)).style(get_percentage_style(percentage, styles))
+}
+
+/// Formats memory used and limit as "used / limit", or "used / no limit"
+fn format_memory_usage(stats: &ContainerStats) -> String {
+    let Some(used) = stats.memory_used_bytes else {
+        return "-".to_string();
+    };
+
+    match stats.memory_limit_bytes {
+        Some(limit) => format!("{} / {}", format_memory(used), format_memory(limit)),
+        None => format!("{} / no limit", format_memory(used)),
+    }
+}
+
+/// Formats a memory size using binary units like `docker stats` (KiB, MiB, GiB)
+fn format_memory(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = KIB * 1024.0;
+    const GIB: f64 = MIB * 1024.0;
+
+    let bytes = bytes as f64;
+    if bytes >= GIB {
+        format!("{:.2}GiB", bytes / GIB)
+    } else if bytes >= MIB {
+        format!("{:.1}MiB", bytes / MIB)
+    } else if bytes >= KIB {
+        format!("{:.1}KiB", bytes / KIB)
+    } else {
+        format!("{:.0}B", bytes)
+    }
+}
+
+/// Formats the PIDs count as current/limit, colored by how close it is to the limit
+fn format_pids(stats: &ContainerStats, styles: &UiStyles) -> (String, Style) {
+    match (stats.pids_current, stats.pids_limit) {
+        (Some(current), Some(limit)) => {
+            let percentage = current as f64 / limit as f64 * 100.0;
+            (
+                format!("{}/{}", current, limit),
+                get_percentage_style(percentage, styles),
+            )
+        }
+        (Some(current), None) => (format!("{}/∞", current), Style::default()),
+        (None, _) => ("-".to_string(), Style::default()),
+    }
+}
+
+/// Formats a byte count into a human-readable string (B, KB, MB, GB)
+fn format_bytes(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
+
+    let bytes = bytes as f64;
+    if bytes >= GB {
+        format!("{:.2}GB", bytes / GB)
+    } else if bytes >= MB {
+        format!("{:.2}MB", bytes / MB)
+    } else if bytes >= KB {
+        format!("{:.1}KB", bytes / KB)
+    } else {
+        format!("{:.0}B", bytes)
+    }
+}
+
 /// Formats bytes per second into a human-readable string (KB/s, MB/s, GB/s)
 fn format_bytes_per_sec(bytes_per_sec: f64) -> String {
     const KB: f64 = 1024.0;
@@ -253,14 +2026,14 @@ pub fn get_percentage_style(value: f64, styles: &UiStyles) -> Style {
 }
 
 /// Creates the table header row
-fn create_header_row(styles: &UiStyles, show_host_column: bool) -> Row<'static> {
-    let mut headers = vec!["ID", "Name"];
-
-    if show_host_column {
-        headers.push("Host");
-    }
-
-    headers.extend(vec!["CPU %", "Memory %", "Net TX", "Net RX", "Status"]);
+fn create_header_row(styles: &UiStyles, columns: &[Column], cpu_mode: CpuMode) -> Row<'static> {
+    let headers = columns.iter().map(|column| match column {
+        Column::Cpu => match cpu_mode {
+            CpuMode::PerCore => "CPU % (per core)",
+            CpuMode::OfLimit => "CPU % (of limit)",
+        },
+        column => column.title(),
+    });
 
     Row::new(headers).style(styles.header).bottom_margin(1)
 }
@@ -270,37 +2043,29 @@ fn create_table<'a>(
     rows: Vec<Row<'a>>,
     header: Row<'static>,
     container_count: usize,
+    smoothing: &str,
     styles: &UiStyles,
-    show_host_column: bool,
+    columns: &[Column],
+    status_message: Option<&str>,
 ) -> Table<'a> {
-    let mut constraints = vec![
-        Constraint::Length(12), // Container ID
-        Constraint::Fill(1),    // Name (flexible)
-    ];
+    let constraints: Vec<Constraint> = columns.iter().map(|c| c.constraint()).collect();
 
-    if show_host_column {
-        constraints.push(Constraint::Length(20)); // Host
-    }
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Docker Container CPU Monitor - {} containers, smoothing: {} ('?' for keys, 'q' to quit)",
+            container_count, smoothing
+        ))
+        .style(styles.border);
 
-    constraints.extend(vec![
-        Constraint::Length(28), // CPU progress bar (20 chars + " 100.0%")
-        Constraint::Length(28), // Memory progress bar (20 chars + " 100.0%")
-        Constraint::Length(12), // Network TX (1.23MB/s)
-        Constraint::Length(12), // Network RX (4.56MB/s)
-        Constraint::Length(15), // Status
-    ]);
+    // Show the last action's error (if any) in the bottom border
+    if let Some(message) = status_message {
+        block = block.title_bottom(Line::from(Span::styled(message.to_string(), styles.high)));
+    }
 
     Table::new(rows, constraints)
         .header(header)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(format!(
-                    "Docker Container CPU Monitor - {} containers (↑/↓ to navigate, 'q' to quit)",
-                    container_count
-                ))
-                .style(styles.border),
-        )
+        .block(block)
         .row_highlight_style(styles.selected)
 }
 
@@ -366,4 +2131,79 @@ mod tests {
             "100% should be red"
         );
     }
+
+    #[test]
+    fn test_visible_columns_drop_secondary_columns_first() {
+        assert_eq!(visible_columns(400, true).len(), COLUMNS.len());
+        assert!(!visible_columns(400, false).contains(&Column::Host));
+
+        // Trends go first, and the rest still leaves Name its minimum width
+        let columns = visible_columns(220, false);
+        assert!(!columns.contains(&Column::CpuTrend));
+        assert!(columns.contains(&Column::DiskRead));
+
+        let columns = visible_columns(120, false);
+        assert_eq!(
+            columns,
+            vec![
+                Column::Id,
+                Column::Name,
+                Column::Cpu,
+                Column::Memory,
+                Column::Health,
+                Column::Status
+            ]
+        );
+        let used: u16 = columns.iter().map(|c| c.width()).sum::<u16>() + columns.len() as u16 + 1;
+        assert!(used <= 120);
+
+        // Core columns are kept even when they don't fit
+        assert_eq!(visible_columns(40, true).len(), 5);
+    }
+
+    #[test]
+    fn test_format_memory_usage() {
+        let mut stats = ContainerStats {
+            memory_used_bytes: Some(512 * 1024 * 1024),
+            memory_limit_bytes: Some(2 * 1024 * 1024 * 1024),
+            ..Default::default()
+        };
+        assert_eq!(format_memory_usage(&stats), "512.0MiB / 2.00GiB");
+
+        stats.memory_limit_bytes = None;
+        assert_eq!(format_memory_usage(&stats), "512.0MiB / no limit");
+
+        stats.memory_used_bytes = None;
+        assert_eq!(format_memory_usage(&stats), "-");
+    }
+
+    #[test]
+    fn test_create_progress_bar_scales_to_max() {
+        // 200% of 4 cores fills half the bar without clamping the label
+        let bar = create_progress_bar(200.0, 400.0, 10);
+        assert_eq!(bar, "█████░░░░░ 200.0%");
+
+        let bar = create_progress_bar(150.0, 100.0, 4);
+        assert_eq!(bar, "████ 150.0%");
+    }
+
+    #[test]
+    fn test_cpu_display_modes() {
+        let stats = ContainerStats {
+            cpu: 200.0,
+            cpu_limit_cores: 4.0,
+            ..Default::default()
+        };
+
+        assert_eq!(cpu_display(&stats, CpuMode::PerCore), (200.0, 400.0));
+        assert_eq!(cpu_display(&stats, CpuMode::OfLimit), (50.0, 100.0));
+
+        // A container limited to half a core is fully busy at 50% of one core
+        let stats = ContainerStats {
+            cpu: 50.0,
+            cpu_limit_cores: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(cpu_display(&stats, CpuMode::OfLimit), (100.0, 100.0));
+    }
 }